@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MIT
+//! Parsing card files into lines of bits
+
+use punch_card_source::{chars, tokens, ParseCardError, Token};
+
+/// A parse error, at a 1-based line and column of the card file
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+	pub line: usize,
+	pub column: usize,
+	pub message: String,
+}
+
+impl From<ParseCardError> for Error {
+	fn from(error: ParseCardError) -> Self {
+		Self {
+			line: error.line,
+			column: error.column,
+			message: format!("expected {}", error.expected),
+		}
+	}
+}
+
+/// How the holes of a card file are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+	/// The same syntax as card tuples, `..=` for a one and `..` for a zero, each line ending in `..,`
+	Ranges,
+	/// One character per hole, one line of the file per line of the card
+	Glyphs { one: char, zero: char },
+}
+
+/// Parses a card file, checking that all lines are the same length
+pub fn parse(text: &str, syntax: Syntax) -> Result<Vec<Vec<bool>>, Error> {
+	let lines = match syntax {
+		Syntax::Ranges => parse_ranges(text)?,
+		Syntax::Glyphs { one, zero } => parse_glyphs(text, one, zero)?,
+	};
+	if lines.is_empty() {
+		return Err(Error {
+			line: 1,
+			column: 1,
+			message: "card has no lines".into(),
+		});
+	}
+	let width = lines[0].1.len();
+	for &((line, column), ref bits) in &lines {
+		if bits.len() != width {
+			return Err(Error {
+				line,
+				column,
+				message: format!(
+					"line has {} columns, but the first line has {width}",
+					bits.len()
+				),
+			});
+		}
+	}
+	Ok(lines.into_iter().map(|(_, bits)| bits).collect())
+}
+
+/// A card line along with the position in the file it starts at
+type Line = ((usize, usize), Vec<bool>);
+
+/// Parses `..`/`..=` lines
+fn parse_ranges(text: &str) -> Result<Vec<Line>, Error> {
+	let mut lines = Vec::new();
+	// the current card line, along with where its first column is
+	let mut current: Option<Line> = None;
+	for token in tokens(text) {
+		match token? {
+			(at, Token::Hole(bit)) => current.get_or_insert((at, Vec::new())).1.push(bit),
+			(at, Token::LineEnd) => lines.push(current.take().unwrap_or((at, Vec::new()))),
+			(_, Token::End) => {}
+		}
+	}
+	Ok(lines)
+}
+
+/// Whether glyph files with these glyphs are read one character at a time, without skipping whitespace or comments
+pub fn verbatim(one: char, zero: char) -> bool {
+	[one, zero].iter().any(|&c| c.is_whitespace() || c == '/')
+}
+
+/// Parses glyph lines, where every non-empty line of the file is a line of the card.
+///
+/// If a glyph is whitespace or `/`, every line is a line of the card and every character of it is a glyph instead.
+fn parse_glyphs(text: &str, one: char, zero: char) -> Result<Vec<Line>, Error> {
+	let bit = |line, column, c| match c {
+		c if c == one => Ok(true),
+		c if c == zero => Ok(false),
+		c => Err(Error {
+			line,
+			column,
+			message: format!("expected `{one}` or `{zero}`, found `{c}`"),
+		}),
+	};
+	if verbatim(one, zero) {
+		return text
+			.lines()
+			.enumerate()
+			.map(|(line, text)| {
+				let bits = text
+					.chars()
+					.enumerate()
+					.map(|(column, c)| bit(line + 1, column + 1, c))
+					.collect::<Result<_, _>>()?;
+				Ok(((line + 1, 1), bits))
+			})
+			.collect();
+	}
+	let mut lines: Vec<Line> = Vec::new();
+	for (line, column, c) in chars(text) {
+		let bit = bit(line, column, c)?;
+		match lines.last_mut() {
+			Some(((last, _), bits)) if *last == line => bits.push(bit),
+			_ => lines.push(((line, column), vec![bit])),
+		}
+	}
+	Ok(lines)
+}
+
+/// Writes card lines as tuple source, the same format as `punch_card::render::write_card`
+pub fn to_source(lines: &[Vec<bool>]) -> String {
+	let mut out = String::from("(");
+	for bits in lines {
+		for &bit in bits {
+			out.push_str(if bit { "..=" } else { ".. " });
+		}
+		out.push_str("..,");
+	}
+	out.push(')');
+	out
+}
@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: MIT
+//! Parsing structs and enums for `#[derive(FromPunchCard)]`
+
+use std::iter::Peekable;
+
+use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
+
+/// An error message along with where it happened
+pub type Error = (String, Span);
+
+type Tokens = Peekable<proc_macro::token_stream::IntoIter>;
+
+/// The options of a `#[punch(...)]` attribute
+#[derive(Default)]
+struct Options {
+	/// `little` or `big`, as the name of an `Endian` variant
+	endian: Option<&'static str>,
+	/// `bytes = N`, for integers narrower than their type
+	bytes: Option<usize>,
+	/// The integer type from `#[repr(...)]`, only used for enums
+	repr: Option<String>,
+}
+
+/// Reads the outer attributes in front of an item, variant, or field
+fn attributes(tokens: &mut Tokens) -> Result<Options, Error> {
+	let mut options = Options::default();
+	while let Some(TokenTree::Punct(punct)) = tokens.peek() {
+		if punct.as_char() != '#' {
+			break;
+		}
+		tokens.next();
+		let Some(TokenTree::Group(group)) = tokens.next() else {
+			return Err(("expected an attribute".into(), Span::call_site()));
+		};
+		let mut inner = group.stream().into_iter();
+		let name = match inner.next() {
+			Some(TokenTree::Ident(name)) => name.to_string(),
+			_ => continue,
+		};
+		let args = match inner.next() {
+			Some(TokenTree::Group(args)) if args.delimiter() == Delimiter::Parenthesis => args,
+			_ => continue,
+		};
+		match name.as_str() {
+			"punch" => punch_options(args.stream(), &mut options)?,
+			"repr" => {
+				for token in args.stream() {
+					let TokenTree::Ident(ident) = token else {
+						continue;
+					};
+					match ident.to_string().as_str() {
+						repr @ ("u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32"
+						| "i64" | "i128") => options.repr = Some(repr.into()),
+						repr @ ("usize" | "isize") => {
+							return Err((
+								format!("`#[repr({repr})]` enums can't be read from cards, use a fixed-size integer like `u8` to `u128` or `i8` to `i128`"),
+								ident.span(),
+							))
+						}
+						// `C`, `align(...)` and the like don't change the discriminant
+						_ => {}
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+	Ok(options)
+}
+
+/// Reads the inside of a `#[punch(...)]` attribute
+fn punch_options(stream: TokenStream, options: &mut Options) -> Result<(), Error> {
+	let mut tokens = stream.into_iter();
+	while let Some(token) = tokens.next() {
+		match &token {
+			TokenTree::Ident(ident) if ident.to_string() == "little" => {
+				options.endian = Some("Little")
+			}
+			TokenTree::Ident(ident) if ident.to_string() == "big" => options.endian = Some("Big"),
+			TokenTree::Ident(ident) if ident.to_string() == "bytes" => {
+				match (tokens.next(), tokens.next()) {
+					(Some(TokenTree::Punct(eq)), Some(TokenTree::Literal(bytes)))
+						if eq.as_char() == '=' =>
+					{
+						options.bytes =
+							Some(bytes.to_string().parse().map_err(|_| {
+								("expected an amount of bytes".into(), bytes.span())
+							})?)
+					}
+					_ => return Err(("expected `bytes = N`".into(), ident.span())),
+				}
+			}
+			_ => {
+				return Err((
+					"expected `little`, `big`, or `bytes = N`".into(),
+					token.span(),
+				))
+			}
+		}
+		match tokens.next() {
+			Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => {}
+			Some(token) => return Err(("expected `,`".into(), token.span())),
+			None => {}
+		}
+	}
+	Ok(())
+}
+
+/// Skips `pub`, `pub(crate)` and the like
+fn visibility(tokens: &mut Tokens) {
+	if matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+		tokens.next();
+		if matches!(tokens.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+		{
+			tokens.next();
+		}
+	}
+}
+
+/// Splits the inside of a group at the commas that aren't inside angle brackets
+fn split_commas(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+	let mut parts = vec![Vec::new()];
+	let mut depth = 0usize;
+	for token in stream {
+		if let TokenTree::Punct(punct) = &token {
+			match punct.as_char() {
+				'<' => depth += 1,
+				'>' => depth = depth.saturating_sub(1),
+				',' if depth == 0 => {
+					parts.push(Vec::new());
+					continue;
+				}
+				_ => {}
+			}
+		}
+		parts.last_mut().expect("never empty").push(token);
+	}
+	parts.retain(|part| !part.is_empty());
+	parts
+}
+
+/// A field of a struct, in order
+struct Field {
+	/// The name, or [`None`] for tuple structs
+	name: Option<String>,
+	ty: String,
+	options: Options,
+}
+
+/// Parses the fields of a struct, named or not
+fn fields(stream: TokenStream, named: bool) -> Result<Vec<Field>, Error> {
+	split_commas(stream)
+		.into_iter()
+		.map(|part| {
+			let mut tokens = TokenStream::from_iter(part).into_iter().peekable();
+			let options = attributes(&mut tokens)?;
+			visibility(&mut tokens);
+			let name = if named {
+				let name = tokens.next().map(|name| name.to_string());
+				tokens.next();
+				name
+			} else {
+				None
+			};
+			Ok(Field {
+				name,
+				ty: TokenStream::from_iter(tokens).to_string(),
+				options,
+			})
+		})
+		.collect()
+}
+
+/// Parses the variant names of a fieldless enum
+fn variants(stream: TokenStream) -> Result<Vec<String>, Error> {
+	split_commas(stream)
+		.into_iter()
+		.map(|part| {
+			let mut tokens = TokenStream::from_iter(part).into_iter().peekable();
+			attributes(&mut tokens)?;
+			let name = match tokens.next() {
+				Some(TokenTree::Ident(name)) => name,
+				token => {
+					let span = token.map_or(Span::call_site(), |token| token.span());
+					return Err(("expected a variant".into(), span));
+				}
+			};
+			match tokens.next() {
+				None => Ok(name.to_string()),
+				Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => Ok(name.to_string()),
+				Some(token) => Err((
+					"only enums without fields can be read from cards".into(),
+					token.span(),
+				)),
+			}
+		})
+		.collect()
+}
+
+/// The byte order of a value, overridden by its options
+fn endian(options: &Options, outer: &str) -> String {
+	match options.endian {
+		Some(endian) => format!("::punch_card::Endian::{endian}"),
+		None => outer.into(),
+	}
+}
+
+/// Generates `FromPunchCard` for a struct or enum
+pub fn derive(input: TokenStream) -> Result<String, Error> {
+	let mut tokens = input.into_iter().peekable();
+	let options = attributes(&mut tokens)?;
+	visibility(&mut tokens);
+	let kind = match tokens.next() {
+		Some(TokenTree::Ident(kind)) => kind,
+		_ => return Err(("expected a struct or enum".into(), Span::call_site())),
+	};
+	let name = match tokens.next() {
+		Some(TokenTree::Ident(name)) => name.to_string(),
+		_ => return Err(("expected a name".into(), kind.span())),
+	};
+	let body = match tokens.next() {
+		Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+			return Err((
+				"generic types can't be read from cards".into(),
+				punct.span(),
+			))
+		}
+		Some(TokenTree::Group(group)) => Some(group),
+		_ => None,
+	};
+	let outer = endian(&options, "endian");
+	match (kind.to_string().as_str(), body) {
+		("struct", body) => {
+			let (fields, named) = match body {
+				Some(body) if body.delimiter() == Delimiter::Brace => {
+					(fields(body.stream(), true)?, true)
+				}
+				Some(body) => (fields(body.stream(), false)?, false),
+				None => (Vec::new(), false),
+			};
+			Ok(derive_struct(&name, &outer, &fields, named))
+		}
+		("enum", Some(body)) => {
+			let repr = options.repr.as_deref().unwrap_or("u8");
+			Ok(derive_enum(&name, &outer, repr, &variants(body.stream())?))
+		}
+		_ => Err((
+			"only structs and enums can be read from cards".into(),
+			kind.span(),
+		)),
+	}
+}
+
+fn derive_struct(name: &str, outer: &str, fields: &[Field], named: bool) -> String {
+	let mut bytes = String::from("0");
+	let mut checks = String::new();
+	let mut reads = String::new();
+	let mut values = String::new();
+	for (i, field) in fields.iter().enumerate() {
+		let ty = &field.ty;
+		let endian = endian(&field.options, "endian");
+		let read = match field.options.bytes {
+			Some(width) => {
+				checks.push_str(&format!(
+					"const _: () = ::core::assert!({width} <= <{ty} as ::punch_card::internal::FromBytes>::BYTES, \"the field is narrower than `bytes`\");"
+				));
+				bytes.push_str(&format!(" + {width}"));
+				format!(
+					"let width = {width}; let value = ::punch_card::internal::from_narrow_bytes::<{ty}>(&bytes[offset..offset + width], {endian});"
+				)
+			}
+			None => {
+				bytes.push_str(&format!(" + <{ty} as ::punch_card::FromPunchCard>::BYTES"));
+				format!(
+					"let width = <{ty} as ::punch_card::FromPunchCard>::BYTES; let value = <{ty} as ::punch_card::FromPunchCard>::from_punch_bytes(&bytes[offset..offset + width], {endian}).map_err(|column| offset + column)?;"
+				)
+			}
+		};
+		reads.push_str(&format!(
+			"let field_{i} = {{ {read} offset += width; value }};"
+		));
+		match &field.name {
+			Some(name) if named => values.push_str(&format!("{name}: field_{i},")),
+			_ => values.push_str(&format!("field_{i},")),
+		}
+	}
+	let value = match (named, fields.is_empty()) {
+		(true, _) => format!("Self {{ {values} }}"),
+		(false, false) => format!("Self({values})"),
+		(false, true) => "Self".into(),
+	};
+	format!(
+		"{checks}
+		impl ::punch_card::FromPunchCard for {name} {{
+			const BYTES: usize = {bytes};
+			#[allow(unused_variables, unused_mut, unused_assignments)]
+			fn from_punch_bytes(bytes: &[u8], endian: ::punch_card::Endian) -> ::core::result::Result<Self, usize> {{
+				let endian = {outer};
+				let mut offset = 0;
+				{reads}
+				::core::result::Result::Ok({value})
+			}}
+		}}"
+	)
+}
+
+fn derive_enum(name: &str, outer: &str, repr: &str, variants: &[String]) -> String {
+	let mut fits = String::new();
+	let mut checks = String::new();
+	for variant in variants {
+		// `as` would silently cut off the discriminants that don't fit
+		fits.push_str(&format!(
+			"const _: () = ::core::assert!({name}::{variant} as i128 == ({name}::{variant} as {repr}) as i128, \"the discriminant of `{variant}` doesn't fit in `{repr}`, pick a wider `#[repr]`\");"
+		));
+		checks.push_str(&format!(
+			"if value == Self::{variant} as {repr} {{ return ::core::result::Result::Ok(Self::{variant}); }}"
+		));
+	}
+	format!(
+		"{fits}
+		impl ::punch_card::FromPunchCard for {name} {{
+			const BYTES: usize = <{repr} as ::punch_card::FromPunchCard>::BYTES;
+			fn from_punch_bytes(bytes: &[u8], endian: ::punch_card::Endian) -> ::core::result::Result<Self, usize> {{
+				let value = <{repr} as ::punch_card::FromPunchCard>::from_punch_bytes(bytes, {outer})?;
+				{checks}
+				::core::result::Result::Err(0)
+			}}
+		}}"
+	)
+}
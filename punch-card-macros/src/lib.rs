@@ -0,0 +1,320 @@
+// SPDX-License-Identifier: MIT
+//! Procedural macros for [punch-card](https://docs.rs/punch-card), use them through that crate instead.
+#![deny(missing_docs)]
+
+use std::path::PathBuf;
+
+use proc_macro::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+mod card;
+mod derive;
+#[cfg(test)]
+mod tests;
+
+use card::Syntax;
+
+/// Expands to `compile_error!(message)` at `span`
+fn compile_error(message: &str, span: Span) -> TokenStream {
+	let mut message = Literal::string(message);
+	message.set_span(span);
+	let tokens: TokenStream = "::core::compile_error!".parse().unwrap();
+	tokens
+		.into_iter()
+		.chain([TokenTree::Group(Group::new(
+			Delimiter::Parenthesis,
+			TokenTree::Literal(message).into(),
+		))])
+		.map(|mut token| {
+			token.set_span(span);
+			token
+		})
+		.collect()
+}
+
+/// Reads the contents of a string or character literal, without handling escapes
+fn literal_contents(
+	token: Option<TokenTree>,
+	quote: char,
+	what: &str,
+) -> Result<String, (String, Span)> {
+	match token {
+		Some(TokenTree::Literal(literal)) => {
+			let text = literal.to_string();
+			text.strip_prefix(quote)
+				.and_then(|text| text.strip_suffix(quote))
+				.filter(|text| !text.contains('\\'))
+				.map(str::to_owned)
+				.ok_or_else(|| (format!("expected {what}"), literal.span()))
+		}
+		Some(token) => Err((format!("expected {what}"), token.span())),
+		None => Err((format!("expected {what}"), Span::call_site())),
+	}
+}
+
+/// Reads a character literal
+fn glyph(token: Option<TokenTree>, what: &str) -> Result<char, (String, Span)> {
+	let span = token.as_ref().map_or(Span::call_site(), TokenTree::span);
+	let glyph = literal_contents(token, '\'', what)?;
+	glyph
+		.chars()
+		.next()
+		.ok_or((format!("expected {what}"), span))
+}
+
+/// Skips a `,` between arguments, returning whether there was one
+fn comma(tokens: &mut impl Iterator<Item = TokenTree>) -> Result<bool, (String, Span)> {
+	match tokens.next() {
+		Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => Ok(true),
+		Some(token) => Err(("expected `,`".into(), token.span())),
+		None => Ok(false),
+	}
+}
+
+/// Parses the arguments to [`include_card!`], a path and optionally a pair of glyphs
+fn arguments(input: TokenStream) -> Result<(String, Span, Syntax), (String, Span)> {
+	let mut tokens = input.into_iter();
+	let path_token = tokens.next();
+	let span = path_token
+		.as_ref()
+		.map_or(Span::call_site(), TokenTree::span);
+	let path = literal_contents(path_token, '"', "a file path")?;
+	if !comma(&mut tokens)? {
+		return Ok((path, span, Syntax::Ranges));
+	}
+	let one = glyph(tokens.next(), "a glyph for punched holes")?;
+	if !comma(&mut tokens)? {
+		return Err((
+			"expected a glyph for unpunched holes".into(),
+			Span::call_site(),
+		));
+	}
+	let zero = glyph(tokens.next(), "a glyph for unpunched holes")?;
+	if comma(&mut tokens)? {
+		if let Some(token) = tokens.next() {
+			return Err(("unexpected argument".into(), token.span()));
+		}
+	}
+	if one == zero {
+		return Err(("the two glyphs have to be different".into(), span));
+	}
+	Ok((path, span, Syntax::Glyphs { one, zero }))
+}
+
+/// Finds a file relative to the file the macro was called in, like [`include_str!`]
+fn resolve(path: &str, span: Span) -> PathBuf {
+	let base = span
+		.local_file()
+		.and_then(|file| file.parent().map(PathBuf::from))
+		.or_else(|| std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from))
+		.unwrap_or_default();
+	// `include_bytes!` in the expansion would resolve relative paths against the calling file again
+	std::path::absolute(base.join(path)).unwrap_or_else(|_| base.join(path))
+}
+
+/// Loads a card from a file at compile time, expanding to the same tuple as writing the card inline.
+///
+/// The path is relative to the file the macro is called in, like [`include_str!`].
+/// By default the file uses the same `..`/`..=` syntax as inline cards, optionally wrapped in parentheses and with `//` comments.
+/// A pair of glyphs for punched and unpunched holes can be given instead, with each line of the file being one line of the card.
+/// Glyph files skip whitespace and `//` comments, unless one of the glyphs is whitespace or `/`, then every character of a line is one hole:
+///
+/// ```rust,ignore
+/// use punch_card::{include_card, PunchCard};
+///
+/// let font: [u8; 64] = include_card!("font.card").punch_card();
+/// let glyph: [u8; 8] = include_card!("glyph.card", '#', '.').punch_card();
+/// ```
+///
+/// Malformed files fail to compile, pointing to the line and column of the file.
+///
+/// Only available with the `macros` feature, which is enabled by default.
+#[proc_macro]
+pub fn include_card(input: TokenStream) -> TokenStream {
+	let (path, span, syntax) = match arguments(input) {
+		Ok(arguments) => arguments,
+		Err((message, span)) => return compile_error(&message, span),
+	};
+	let file = resolve(&path, span);
+	let text = match std::fs::read_to_string(&file) {
+		Ok(text) => text,
+		Err(error) => {
+			return compile_error(&format!("couldn't read {}: {error}", file.display()), span)
+		}
+	};
+	let lines = match card::parse(&text, syntax) {
+		Ok(lines) => lines,
+		Err(error) => {
+			return compile_error(
+				&format!("{path}:{}:{}: {}", error.line, error.column, error.message),
+				span,
+			)
+		}
+	};
+	// including the file makes cargo rebuild when it changes
+	let file = Literal::string(&file.to_string_lossy());
+	format!(
+		"{{ const _: &[u8] = ::core::include_bytes!({file}); {} }}",
+		card::to_source(&lines)
+	)
+	.parse()
+	.unwrap()
+}
+
+/// The span of every glyph of a card, one list per line
+type Spans = Vec<Vec<Span>>;
+
+/// Reads the body of [`punch!`], an optional pair of glyphs followed by the lines of the card.
+///
+/// Returns the glyphs as text with one line per line of the source, along with the span of every glyph.
+fn glyph_lines(input: TokenStream) -> Result<(Syntax, String, Spans), (String, Span)> {
+	let mut tokens = input.into_iter().peekable();
+	let mut syntax = Syntax::Glyphs {
+		one: '#',
+		zero: '.',
+	};
+	if matches!(tokens.peek(), Some(TokenTree::Literal(literal)) if literal.to_string().starts_with('\''))
+	{
+		let span = tokens.peek().map_or(Span::call_site(), TokenTree::span);
+		let one = glyph(tokens.next(), "a glyph for punched holes")?;
+		if !comma(&mut tokens)? {
+			return Err(("expected a glyph for unpunched holes".into(), span));
+		}
+		let zero = glyph(tokens.next(), "a glyph for unpunched holes")?;
+		match tokens.next() {
+			Some(TokenTree::Punct(punct)) if punct.as_char() == ';' => {}
+			token => {
+				let span = token.map_or(Span::call_site(), |token| token.span());
+				return Err(("expected `;` after the glyphs".into(), span));
+			}
+		}
+		if one == zero {
+			return Err(("the two glyphs have to be different".into(), span));
+		}
+		// whitespace never reaches the macro as a token
+		if one.is_whitespace() || zero.is_whitespace() {
+			return Err(("glyphs can't be whitespace".into(), span));
+		}
+		syntax = Syntax::Glyphs { one, zero };
+	}
+	let mut text = String::new();
+	let mut spans: Spans = Vec::new();
+	let mut line = None;
+	for token in tokens {
+		if let TokenTree::Group(group) = &token {
+			return Err(("expected glyphs".into(), group.span_open()));
+		}
+		// tokens on the same line of the source are on the same line of the card
+		if line != Some(token.span().line()) {
+			if line.is_some() {
+				text.push('\n');
+			}
+			line = Some(token.span().line());
+			spans.push(Vec::new());
+		}
+		for c in token.to_string().chars() {
+			text.push(c);
+			spans.last_mut().expect("pushed above").push(token.span());
+		}
+	}
+	Ok((syntax, text, spans))
+}
+
+/// Writes a card with one glyph per hole, expanding to the same array as calling `punch_card` on it.
+///
+/// Each line of the source is one line of the card, with `#` for punched holes and `.` for unpunched ones.
+/// A different pair of glyphs can be given first, followed by a `;`:
+///
+/// ```rust,ignore
+/// use punch_card::punch;
+///
+/// let arrow: [u8; 5] = punch! {
+///     . . # . .
+///     . # # # .
+///     # . # . #
+///     . . # . .
+/// };
+/// let flags: [bool; 4] = punch! { 'X', '_'; X_XX };
+/// ```
+///
+/// The card is decoded at compile time with `punch_card_const`, so it can also initialize `const` and `static` items,
+/// but that also limits it to lines of up to 1024 columns, even with the `nightly` feature.
+/// Glyphs have to be valid Rust tokens other than whitespace, and lines of different lengths fail to compile.
+///
+/// The expansion refers to `::punch_card::punch_card_const`, so the `punch-card` crate has to be a dependency under that name.
+///
+/// Only available with the `macros` feature, which is enabled by default.
+#[proc_macro]
+pub fn punch(input: TokenStream) -> TokenStream {
+	let (syntax, text, spans) = match glyph_lines(input) {
+		Ok(lines) => lines,
+		Err((message, span)) => return compile_error(&message, span),
+	};
+	let lines = match card::parse(&text, syntax) {
+		Ok(lines) => lines,
+		Err(error) => {
+			let span = spans
+				.get(error.line - 1)
+				.and_then(|line| line.get(error.column - 1))
+				.copied()
+				.unwrap_or(Span::call_site());
+			return compile_error(&error.message, span);
+		}
+	};
+	format!(
+		"::punch_card::punch_card_const::<_, {}>({})",
+		lines[0].len(),
+		card::to_source(&lines)
+	)
+	.parse()
+	.unwrap()
+}
+
+/// Implements `FromPunchCard` for a struct or a fieldless enum, for reading it from the byte columns of a card with `punch_card_decode`.
+///
+/// Struct fields are read in order, each from as many columns as its type has bytes.
+/// Enums are read as their `#[repr]` integer, from `u8` to `u128` or `i8` to `i128` and [`u8`] by default, and fail on values that aren't one of their discriminants.
+/// Discriminants that don't fit in that integer fail to compile.
+///
+/// The byte order of integers comes from `punch_card_decode`, or from a `#[punch(little)]` or `#[punch(big)]` attribute on the type or a field.
+/// Integer fields wider than a byte can also be narrower than their type, like `#[punch(bytes = 3)]` for a 24-bit [`u32`], with signed fields sign-extended:
+///
+/// ```rust,ignore
+/// use punch_card::{Endian, FromPunchCard, PunchCard};
+///
+/// #[derive(FromPunchCard)]
+/// #[repr(u8)]
+/// enum Kind {
+///     Text = 1,
+///     Image = 2,
+/// }
+///
+/// #[derive(FromPunchCard)]
+/// struct Header {
+///     kind: Kind,
+///     #[punch(bytes = 3)]
+///     length: u32,
+///     #[punch(little)]
+///     checksum: u16,
+/// }
+///
+/// let header: Header = include_card!("header.card").punch_card_decode(Endian::Big)?;
+/// ```
+///
+/// The generated impl refers to `::punch_card`, so the `punch-card` crate has to be a dependency under that name.
+///
+/// Only available with the `macros` feature, which is enabled by default.
+#[proc_macro_derive(FromPunchCard, attributes(punch))]
+pub fn from_punch_card(input: TokenStream) -> TokenStream {
+	match derive::derive(input) {
+		Ok(source) => source.parse().unwrap(),
+		Err((message, span)) => {
+			// derives expand to items, where the macro call needs a `;`
+			let mut semicolon = Punct::new(';', Spacing::Alone);
+			semicolon.set_span(span);
+			compile_error(&message, span)
+				.into_iter()
+				.chain([TokenTree::Punct(semicolon)])
+				.collect()
+		}
+	}
+}
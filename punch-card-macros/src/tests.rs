@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MIT
+//! Tests for the card file parser
+
+use crate::card::{parse, to_source, Error, Syntax};
+
+fn error(line: usize, column: usize, message: &str) -> Result<Vec<Vec<bool>>, Error> {
+	Err(Error {
+		line,
+		column,
+		message: message.into(),
+	})
+}
+
+#[test]
+fn ranges() {
+	let text = "// a comment\n(\n\t..=.. ..,\n\t.. ..=..,\n)\n";
+	let lines = parse(text, Syntax::Ranges).unwrap();
+	assert_eq!(lines, [[true, false], [false, true]]);
+	assert_eq!(to_source(&lines), "(..=.. ..,.. ..=..,)");
+	assert_eq!(
+		parse("..=..,\n.. ..,", Syntax::Ranges).unwrap(),
+		[[true], [false]]
+	);
+}
+
+#[test]
+fn ranges_errors() {
+	assert_eq!(
+		parse("(\n\t..=.. ..,\n\t.. ..,\n)", Syntax::Ranges),
+		error(3, 2, "line has 1 columns, but the first line has 2")
+	);
+	assert_eq!(
+		parse("..=.. ..,\n..=..=,", Syntax::Ranges),
+		error(2, 7, "expected a line ending in `..` before `,`")
+	);
+	assert_eq!(
+		parse("..=.. .x.,", Syntax::Ranges),
+		error(1, 7, "expected `..` or `..=`")
+	);
+	assert_eq!(
+		parse(". ..,", Syntax::Ranges),
+		error(1, 1, "expected `..` or `..=`")
+	);
+	assert_eq!(
+		parse("..=.. ...,", Syntax::Ranges),
+		error(1, 9, "expected `..` or `..=`")
+	);
+	assert_eq!(
+		parse("..=.. .. =..,", Syntax::Ranges),
+		error(1, 10, "expected `..`, `..=` or `,`")
+	);
+	assert_eq!(
+		parse("..=.\n.,", Syntax::Ranges),
+		error(1, 4, "expected `..` or `..=`")
+	);
+	assert_eq!(
+		parse("..=.. ..", Syntax::Ranges),
+		error(1, 9, "expected `,` after the last line")
+	);
+	assert_eq!(
+		parse("(..=.. ..,", Syntax::Ranges),
+		error(1, 11, "expected `)` after the last line")
+	);
+	assert_eq!(
+		parse("// nothing", Syntax::Ranges),
+		error(1, 1, "card has no lines")
+	);
+}
+
+#[test]
+fn glyphs() {
+	let syntax = Syntax::Glyphs {
+		one: '#',
+		zero: '.',
+	};
+	assert_eq!(
+		parse("# . #\n\n. # .\n", syntax).unwrap(),
+		[[true, false, true], [false, true, false]]
+	);
+	assert_eq!(
+		parse("# . #\n. x .\n", syntax),
+		error(2, 3, "expected `#` or `.`, found `x`")
+	);
+}
+
+#[test]
+fn glyphs_verbatim() {
+	let spaces = Syntax::Glyphs {
+		one: '#',
+		zero: ' ',
+	};
+	assert_eq!(
+		parse("# #\n## \n", spaces).unwrap(),
+		[[true, false, true], [true, true, false]]
+	);
+	// a line with its trailing spaces trimmed doesn't quietly lose holes
+	assert_eq!(
+		parse("# #\n##\n", spaces),
+		error(2, 1, "line has 2 columns, but the first line has 3")
+	);
+	assert_eq!(
+		parse("# #\n\n# #\n", spaces),
+		error(2, 1, "line has 0 columns, but the first line has 3")
+	);
+	let slashes = Syntax::Glyphs {
+		one: '/',
+		zero: '.',
+	};
+	assert_eq!(
+		parse("./\n//\n", slashes).unwrap(),
+		[[false, true], [true, true]]
+	);
+}
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: MIT
+//! The grammar of card source for [punch-card](https://docs.rs/punch-card), use it through that crate instead.
+//!
+//! Shared by `Card`'s `FromStr`, `include_card!` and the `punch` tool, so the three can't drift apart.
+#![deny(missing_docs)]
+#![no_std]
+
+use core::fmt;
+use core::iter::Peekable;
+
+/// What was expected where parsing a `Card` failed, see [`ParseCardError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+	/// `..` or `..=`, after a single `.` or instead of `...`
+	Hole,
+	/// `..`, `..=` or `,`
+	Token,
+	/// A line ending in `..` before its `,`
+	LineEnd,
+	/// `,` after the last line
+	Comma,
+	/// `)` after the last line, when the card starts with `(`
+	CloseParen,
+	/// Nothing after the closing `)`
+	End,
+	/// Exactly this many columns in the line
+	Columns(usize),
+	/// Exactly this many lines in the card
+	Lines(usize),
+}
+
+impl fmt::Display for Expected {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Hole => f.write_str("`..` or `..=`"),
+			Self::Token => f.write_str("`..`, `..=` or `,`"),
+			Self::LineEnd => f.write_str("a line ending in `..` before `,`"),
+			Self::Comma => f.write_str("`,` after the last line"),
+			Self::CloseParen => f.write_str("`)` after the last line"),
+			Self::End => f.write_str("the end of the card after `)`"),
+			Self::Columns(columns) => write!(f, "a line of {columns} columns"),
+			Self::Lines(lines) => write!(f, "a card of {lines} lines"),
+		}
+	}
+}
+
+/// An error from parsing card source into a `Card`, at a line and column of the text, both counting from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseCardError {
+	/// Line of the text where parsing failed
+	pub line: usize,
+	/// Character in the line where parsing failed
+	pub column: usize,
+	/// What should have been there instead
+	pub expected: Expected,
+}
+
+impl ParseCardError {
+	/// An error at a `(line, column)` position
+	pub fn at((line, column): (usize, usize), expected: Expected) -> Self {
+		Self {
+			line,
+			column,
+			expected,
+		}
+	}
+}
+
+impl fmt::Display for ParseCardError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"expected {} at line {}, column {}",
+			self.expected, self.line, self.column
+		)
+	}
+}
+
+impl core::error::Error for ParseCardError {}
+
+/// The characters of card source along with their line and column, skipping whitespace and `//` comments
+pub fn chars(text: &str) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+	text.lines().enumerate().flat_map(|(line, text)| {
+		let text = text.find("//").map_or(text, |comment| &text[..comment]);
+		text.chars()
+			.enumerate()
+			.filter(|(_, c)| !c.is_whitespace())
+			.map(move |(column, c)| (line + 1, column + 1, c))
+	})
+}
+
+/// A part of card source, see [`tokens`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+	/// A column of the current line, `..=` for a one and `..` for a zero
+	Hole(bool),
+	/// The `..,` after the last column of a line
+	LineEnd,
+	/// The end of the text, always the last token
+	End,
+}
+
+/// The tokens of card source made of `..` and `..=`, see [`tokens`]
+pub struct Tokens<I: Iterator<Item = (usize, usize, char)>> {
+	chars: Peekable<I>,
+	/// Whether the card started with a `(` that hasn't been closed yet
+	parens: bool,
+	/// The last hole of the current line, which could still be its terminator
+	last: Option<((usize, usize), bool)>,
+	/// One past the last character, for errors at the end of the text
+	end: (usize, usize),
+	done: bool,
+}
+
+/// Splits card source into holes and line ends, each with the line and column it starts at.
+///
+/// Every line has to end in `..,`, optionally with the whole card surrounded by `(` and `)`.
+/// Doesn't check the lengths of lines, the tokens stop after the first error.
+pub fn tokens(text: &str) -> Tokens<impl Iterator<Item = (usize, usize, char)> + '_> {
+	let mut chars = chars(text).peekable();
+	let parens = chars.next_if(|&(_, _, c)| c == '(').is_some();
+	Tokens {
+		chars,
+		parens,
+		last: None,
+		end: (1, 1),
+		done: false,
+	}
+}
+
+impl<I: Iterator<Item = (usize, usize, char)>> Tokens<I> {
+	fn next_token(&mut self) -> Result<((usize, usize), Token), ParseCardError> {
+		while let Some((line, column, c)) = self.chars.next() {
+			let at = (line, column);
+			self.end = (line, column + 1);
+			match c {
+				'.' => {
+					// the characters of a hole can't be split up, like in Rust source
+					if self
+						.chars
+						.next_if(|&next| next == (line, column + 1, '.'))
+						.is_none()
+					{
+						return Err(ParseCardError::at(at, Expected::Hole));
+					}
+					let bit = self
+						.chars
+						.next_if(|&next| next == (line, column + 2, '='))
+						.is_some();
+					// Rust reads `...` as its own token, so `..` can't be followed by another `.`
+					if !bit && self.chars.peek() == Some(&(line, column + 2, '.')) {
+						return Err(ParseCardError::at((line, column + 2), Expected::Hole));
+					}
+					self.end.1 += 1 + bit as usize;
+					// the previous hole wasn't the terminator
+					if let Some((at, bit)) = self.last.replace((at, bit)) {
+						return Ok((at, Token::Hole(bit)));
+					}
+				}
+				',' => match self.last.take() {
+					Some((_, false)) => return Ok((at, Token::LineEnd)),
+					_ => return Err(ParseCardError::at(at, Expected::LineEnd)),
+				},
+				')' if self.parens && self.last.is_none() => {
+					if let Some((line, column, _)) = self.chars.next() {
+						return Err(ParseCardError::at((line, column), Expected::End));
+					}
+					self.parens = false;
+				}
+				')' if self.parens => return Err(ParseCardError::at(at, Expected::Comma)),
+				_ => return Err(ParseCardError::at(at, Expected::Token)),
+			}
+		}
+		if self.last.is_some() {
+			return Err(ParseCardError::at(self.end, Expected::Comma));
+		}
+		if self.parens {
+			return Err(ParseCardError::at(self.end, Expected::CloseParen));
+		}
+		Ok((self.end, Token::End))
+	}
+}
+
+impl<I: Iterator<Item = (usize, usize, char)>> Iterator for Tokens<I> {
+	type Item = Result<((usize, usize), Token), ParseCardError>;
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+		let token = self.next_token();
+		self.done = !matches!(token, Ok((_, Token::Hole(_) | Token::LineEnd)));
+		Some(token)
+	}
+}
@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: MIT
+//! Converts binary files to and from card source.
+//!
+//! ```text
+//! punch encode [--height <bits>] [--lsb] [FILE]
+//! punch decode [--height <bits>] [--lsb] [FILE]
+//! ```
+//!
+//! `encode` reads bytes and writes the source of a card holding them, `decode` reads card source and writes the bytes back.
+//! Card source uses the same syntax as `include_card!` files, `//` comments included.
+//! Reads from stdin if no file (or `-`) is given, and writes to stdout.
+//!
+//! The height is 8, 16, 32, 64 or 128 lines, each column holds `height / 8` bytes in big-endian order, so a card decodes into the same bytes on every target.
+//! The top line is the most significant bit of a column, or the least significant bit with `--lsb`.
+
+use std::io::{self, Read, Write};
+use std::{env, fs, process};
+
+use punch_card::internal::LsbFirst;
+use punch_card::render::{write_card, ToColumn};
+use punch_card_source::{tokens, Expected, ParseCardError, Token};
+
+const USAGE: &str = "usage: punch <encode|decode> [--height <8|16|32|64|128>] [--lsb] [FILE]";
+
+/// Parsed command-line arguments
+struct Args {
+	decode: bool,
+	height: Option<usize>,
+	lsb: bool,
+	path: Option<String>,
+}
+
+/// Parses the arguments after the program name
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+	let decode = match args.next().as_deref() {
+		Some("encode") => false,
+		Some("decode") => true,
+		_ => return Err(USAGE.into()),
+	};
+	let mut out = Args {
+		decode,
+		height: None,
+		lsb: false,
+		path: None,
+	};
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--lsb" => out.lsb = true,
+			"--height" => {
+				let height = args.next().ok_or("--height needs a value")?;
+				out.height = match height.parse() {
+					Ok(height @ (8 | 16 | 32 | 64 | 128)) => Some(height),
+					_ => {
+						return Err(format!(
+							"unsupported height {height}, expected 8, 16, 32, 64 or 128"
+						))
+					}
+				};
+			}
+			_ if out.path.is_none() && (arg == "-" || !arg.starts_with('-')) => {
+				out.path = Some(arg)
+			}
+			_ => return Err(USAGE.into()),
+		}
+	}
+	Ok(out)
+}
+
+fn read_input(path: Option<&str>) -> Result<Vec<u8>, String> {
+	match path {
+		None | Some("-") => {
+			let mut data = Vec::new();
+			io::stdin()
+				.read_to_end(&mut data)
+				.map_err(|e| format!("stdin: {e}"))?;
+			Ok(data)
+		}
+		Some(path) => fs::read(path).map_err(|e| format!("{path}: {e}")),
+	}
+}
+
+/// Writes the source of a card with one column per `height / 8` bytes
+fn encode(data: &[u8], height: usize, lsb: bool) -> Result<String, String> {
+	let width = height / 8;
+	if !data.len().is_multiple_of(width) {
+		return Err(format!(
+			"input is {} bytes, which isn't a multiple of {width}",
+			data.len()
+		));
+	}
+	let columns = data.chunks(width).map(|chunk| {
+		chunk
+			.iter()
+			.fold(0u128, |acc, &byte| acc << 8 | byte as u128)
+	});
+	fn render<T: ToColumn>(columns: impl Iterator<Item = T>) -> String {
+		let columns: Vec<T> = columns.collect();
+		let mut out = String::new();
+		write_card(&mut out, &columns).expect("writing to a String doesn't fail");
+		out.push('\n');
+		out
+	}
+	Ok(match (height, lsb) {
+		(8, false) => render(columns.map(|c| c as u8)),
+		(16, false) => render(columns.map(|c| c as u16)),
+		(32, false) => render(columns.map(|c| c as u32)),
+		(64, false) => render(columns.map(|c| c as u64)),
+		(128, false) => render(columns),
+		(8, true) => render(columns.map(|c| LsbFirst(c as u8))),
+		(16, true) => render(columns.map(|c| LsbFirst(c as u16))),
+		(32, true) => render(columns.map(|c| LsbFirst(c as u32))),
+		(64, true) => render(columns.map(|c| LsbFirst(c as u64))),
+		(128, true) => render(columns.map(LsbFirst)),
+		_ => unreachable!("height is checked when parsing arguments"),
+	})
+}
+
+/// Reads the lines of card source, with the same grammar as `Card` and `include_card!`
+fn parse_lines(text: &str) -> Result<Vec<Vec<bool>>, ParseCardError> {
+	let mut lines: Vec<Vec<bool>> = Vec::new();
+	let mut current = Vec::new();
+	for token in tokens(text) {
+		match token? {
+			(_, Token::Hole(bit)) => current.push(bit),
+			(at, Token::LineEnd) => {
+				if let Some(first) = lines.first().filter(|first| first.len() != current.len()) {
+					return Err(ParseCardError::at(at, Expected::Columns(first.len())));
+				}
+				lines.push(std::mem::take(&mut current));
+			}
+			(_, Token::End) => {}
+		}
+	}
+	Ok(lines)
+}
+
+/// Reads card source back into the bytes it holds
+fn decode(text: &str, height: Option<usize>, lsb: bool) -> Result<Vec<u8>, String> {
+	let mut lines = parse_lines(text).map_err(|e| e.to_string())?;
+	if !matches!(lines.len(), 8 | 16 | 32 | 64 | 128) {
+		return Err(format!(
+			"card has {} lines, expected 8, 16, 32, 64 or 128",
+			lines.len()
+		));
+	}
+	if let Some(height) = height.filter(|&height| height != lines.len()) {
+		return Err(format!("card has {} lines, not {height}", lines.len()));
+	}
+	if lsb {
+		lines.reverse();
+	}
+	let width = lines.len() / 8;
+	let mut out = Vec::new();
+	for column in 0..lines.first().map_or(0, Vec::len) {
+		let bits = lines
+			.iter()
+			.fold(0u128, |acc, line| acc << 1 | line[column] as u128);
+		out.extend_from_slice(&bits.to_be_bytes()[16 - width..]);
+	}
+	Ok(out)
+}
+
+fn run() -> Result<(), String> {
+	let args = parse_args(env::args().skip(1))?;
+	let input = read_input(args.path.as_deref())?;
+	let output = if args.decode {
+		let text = String::from_utf8(input).map_err(|_| "card source isn't valid UTF-8")?;
+		decode(&text, args.height, args.lsb)?
+	} else {
+		encode(&input, args.height.unwrap_or(8), args.lsb)?.into_bytes()
+	};
+	io::stdout()
+		.write_all(&output)
+		.map_err(|e| format!("stdout: {e}"))
+}
+
+fn main() {
+	if let Err(message) = run() {
+		eprintln!("punch: {message}");
+		process::exit(1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn args(args: &[&str]) -> Result<Args, String> {
+		parse_args(args.iter().map(|arg| arg.to_string()))
+	}
+
+	#[test]
+	fn round_trip() {
+		let data: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(37) ^ 0x5A).collect();
+		for height in [8, 16, 32, 64, 128] {
+			for lsb in [false, true] {
+				let text = encode(&data, height, lsb).unwrap();
+				assert_eq!(text.lines().count(), height + 2);
+				assert_eq!(decode(&text, Some(height), lsb).unwrap(), data);
+				assert_eq!(decode(&text, None, lsb).unwrap(), data);
+			}
+		}
+		let msb = encode(&[0x80], 8, false).unwrap();
+		let lsb = encode(&[0x01], 8, true).unwrap();
+		assert_eq!(msb, lsb);
+		assert!(msb.starts_with("(\n\t..=..,\n\t.. ..,\n"));
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			encode(&[1, 2, 3], 16, false),
+			Err("input is 3 bytes, which isn't a multiple of 2".into())
+		);
+		assert_eq!(
+			decode("..=.. ..=..,", None, false),
+			Err("card has 1 lines, expected 8, 16, 32, 64 or 128".into())
+		);
+		let card = encode(&[1, 2], 8, false).unwrap();
+		assert_eq!(
+			decode(&card, Some(16), false),
+			Err("card has 8 lines, not 16".into())
+		);
+		let error = |line, column, expected| ParseCardError {
+			line,
+			column,
+			expected,
+		};
+		assert_eq!(
+			parse_lines("(..=.. ..,\n.. ..,\n)"),
+			Err(error(2, 6, Expected::Columns(2)))
+		);
+		assert_eq!(
+			parse_lines("..=.. ..,\n.. . .,"),
+			Err(error(2, 4, Expected::Hole))
+		);
+		assert_eq!(
+			parse_lines("..=.. .. =..,"),
+			Err(error(1, 10, Expected::Token))
+		);
+		assert_eq!(parse_lines("..=..=,"), Err(error(1, 7, Expected::LineEnd)));
+		assert_eq!(
+			parse_lines("(..=.. ..,"),
+			Err(error(1, 11, Expected::CloseParen))
+		);
+		assert_eq!(
+			parse_lines("(..=.. ..)"),
+			Err(error(1, 10, Expected::Comma))
+		);
+		assert_eq!(parse_lines("..=.. .."), Err(error(1, 9, Expected::Comma)));
+		assert_eq!(
+			decode("..=..x", None, false),
+			Err("expected `..`, `..=` or `,` at line 1, column 6".into())
+		);
+		assert_eq!(
+			parse_lines("// a comment\n(\n\t..=..=..,\n\t.. ..=..,\n)\n").unwrap(),
+			[[true, true], [false, true]]
+		);
+	}
+
+	#[test]
+	fn card_files() {
+		let data = decode(include_str!("../tests/massive.card"), Some(128), false).unwrap();
+		assert!(data.chunks(16).map(|column| column[0]).eq(0..=255));
+		assert!(data
+			.chunks(16)
+			.all(|column| column.iter().all(|&byte| byte == column[0])));
+	}
+
+	#[test]
+	fn arguments() {
+		let parsed = args(&["decode", "--height", "16", "--lsb", "card.txt"]).unwrap();
+		assert!(parsed.decode && parsed.lsb);
+		assert_eq!(
+			(parsed.height, parsed.path.as_deref()),
+			(Some(16), Some("card.txt"))
+		);
+		assert_eq!(
+			args(&["encode", "--height", "12"]).err().unwrap(),
+			"unsupported height 12, expected 8, 16, 32, 64 or 128"
+		);
+		assert_eq!(
+			args(&["encode", "--height"]).err().unwrap(),
+			"--height needs a value"
+		);
+		assert_eq!(args(&["punch"]).err().unwrap(), USAGE);
+	}
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: MIT
+//! Cards as runtime values, for inspecting and editing them.
+//!
+//! A [`Card`] holds the columns of a card of `W` columns and `H` lines, and can be made from any card:
+//!
+//! ```rust
+//! use punch_card::card::Card;
+//!
+//! #[rustfmt::skip]
+//! let mut card: Card<3, 2> = Card::from((
+//!     ..=.. .. ..,
+//!     .. .. ..=..,
+//! ));
+//! assert!(card[(0, 0)] && card[(2, 1)]);
+//! card.clear(0, 0);
+//! card.set(1, 1);
+//! assert_eq!(card.to_string(), "(\n\t.. .. .. ..,\n\t.. ..=..=..,\n)");
+//! ```
+//!
+//! Holes are indexed by `(column, row)`, both counting from the top left, and [`Display`](fmt::Display) writes the same source as [`write_card`].
+//!
+//! Card source is parsed back at runtime with [`str::parse`], using the same syntax as card tuples and `include_card!`:
+//!
+//! ```rust
+//! use punch_card::card::{Card, Expected, ParseCardError};
+//!
+//! let card: Card<3, 2> = "(..=.. .. .., .. .. ..=..,)".parse().unwrap();
+//! assert_eq!(card.columns_as::<u8>(), [0b10, 0, 0b01]);
+//! assert_eq!(
+//!     "..=.. .. ..,\n.. .= .. ..,".parse::<Card<3, 2>>(),
+//!     Err(ParseCardError { line: 2, column: 4, expected: Expected::Hole }),
+//! );
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Index;
+use core::str::FromStr;
+
+use crate::internal::{Bits, FromColumn, LinesMatch, PunchCardInner};
+use crate::render::write_card;
+use punch_card_source::{tokens, Token};
+pub use punch_card_source::{Expected, ParseCardError};
+
+/// A card with `W` columns of `H` lines, up to 128 lines tall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Card<const W: usize, const H: usize> {
+	columns: [Bits<H>; W],
+}
+
+impl<const W: usize, const H: usize> Card<W, H> {
+	/// A card without any holes punched.
+	pub const fn new() -> Self {
+		Self::from_columns([Bits::new(0); W])
+	}
+	/// A card holding the given columns.
+	pub const fn from_columns(columns: [Bits<H>; W]) -> Self {
+		Self { columns }
+	}
+	/// The columns of the card, from left to right.
+	pub const fn columns(&self) -> &[Bits<H>; W] {
+		&self.columns
+	}
+	/// Reads every column as another type, like [`punch_card_as`](crate::PunchCard::punch_card_as).
+	pub fn columns_as<T: FromColumn<Bits<H>>>(&self) -> [T; W] {
+		self.columns.map(T::from_column)
+	}
+	/// Whether the hole at `column` and `row` is punched.
+	///
+	/// # Panics
+	/// If the hole is outside of the card.
+	#[track_caller]
+	pub fn get(&self, column: usize, row: usize) -> bool {
+		assert!(row < H, "row {row} is outside of a card with {H} lines");
+		self.columns[column].get(row)
+	}
+	/// Sets whether the hole at `column` and `row` is punched.
+	///
+	/// # Panics
+	/// If the hole is outside of the card.
+	#[track_caller]
+	pub fn punch(&mut self, column: usize, row: usize, punched: bool) {
+		assert!(row < H, "row {row} is outside of a card with {H} lines");
+		let bits = self.columns[column].to_u128();
+		let mask = 1 << (H - 1 - row);
+		self.columns[column] = Bits::new(if punched { bits | mask } else { bits & !mask });
+	}
+	/// Punches the hole at `column` and `row`, see [`punch`](Card::punch).
+	#[track_caller]
+	pub fn set(&mut self, column: usize, row: usize) {
+		self.punch(column, row, true);
+	}
+	/// Fills in the hole at `column` and `row`, see [`punch`](Card::punch).
+	#[track_caller]
+	pub fn clear(&mut self, column: usize, row: usize) {
+		self.punch(column, row, false);
+	}
+}
+
+impl<const W: usize, const H: usize> Default for Card<W, H> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const W: usize, const H: usize> Index<(usize, usize)> for Card<W, H> {
+	type Output = bool;
+	#[track_caller]
+	fn index(&self, (column, row): (usize, usize)) -> &bool {
+		if self.get(column, row) {
+			&true
+		} else {
+			&false
+		}
+	}
+}
+
+impl<const W: usize, const H: usize> fmt::Display for Card<W, H> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_card(f, &self.columns)
+	}
+}
+
+/// Fails to evaluate if card `C` isn't `W` columns by `H` lines
+struct CardFits<C, const W: usize, const H: usize>(PhantomData<C>);
+impl<C: PunchCardInner, const W: usize, const H: usize> CardFits<C, W, H> {
+	const OK: () = assert!(
+		C::LENGTH == W && C::HEIGHT == H,
+		"the card doesn't match the size of the `Card`"
+	);
+}
+
+impl<C, const W: usize, const H: usize> From<C> for Card<W, H>
+where
+	C: PunchCardInner<Column = Bits<H>> + LinesMatch,
+{
+	fn from(_: C) -> Self {
+		let () = C::LENGTHS_MATCH;
+		let () = CardFits::<C, W, H>::OK;
+		let mut card = Self::new();
+		C::eval_part(&mut card.columns, 0);
+		card
+	}
+}
+
+impl<const W: usize, const H: usize> FromStr for Card<W, H> {
+	type Err = ParseCardError;
+	/// Parses card source, with every line ending in `..,` and optionally surrounded by `(` and `)`.
+	fn from_str(text: &str) -> Result<Self, ParseCardError> {
+		let mut card = Self::new();
+		let (mut row, mut column) = (0, 0);
+		for token in tokens(text) {
+			let (at, token) = token?;
+			match token {
+				Token::Hole(_) | Token::LineEnd if row == H => {
+					return Err(ParseCardError::at(at, Expected::Lines(H)))
+				}
+				Token::Hole(_) if column == W => {
+					return Err(ParseCardError::at(at, Expected::Columns(W)))
+				}
+				Token::Hole(bit) => {
+					card.punch(column, row, bit);
+					column += 1;
+				}
+				Token::LineEnd if column != W => {
+					return Err(ParseCardError::at(at, Expected::Columns(W)))
+				}
+				Token::LineEnd => {
+					row += 1;
+					column = 0;
+				}
+				Token::End if row < H => return Err(ParseCardError::at(at, Expected::Lines(H))),
+				Token::End => {}
+			}
+		}
+		Ok(card)
+	}
+}
@@ -0,0 +1,265 @@
+// SPDX-License-Identifier: MIT
+//! Hollerith cards, the classic 12-line IBM punched cards.
+//!
+//! The lines of a Hollerith card are, from top to bottom, the zone lines 12, 11, and 0, followed by the digit lines 1 through 9.
+//! Each column holds one character, by default decoded into a [`char`] using the IBM 029 keypunch code:
+//!
+//! ```rust
+//! use punch_card::PunchCard;
+//!
+//! #[rustfmt::skip]
+//! assert_eq!((
+//!     ..=..=.. .. .. .. ..,
+//!     .. .. .. .. .. .. ..,
+//!     .. .. .. ..=.. .. ..,
+//!     .. .. .. .. .. .. ..,
+//!     .. .. .. .. .. .. ..,
+//!     .. .. .. .. .. .. ..,
+//!     .. .. .. .. ..=.. ..,
+//!     .. .. .. .. .. .. ..,
+//!     .. .. .. .. .. .. ..,
+//!     .. .. .. .. .. .. ..,
+//!     ..=.. .. .. .. .. ..,
+//!     .. ..=.. ..=.. .. ..,
+//! ).try_punch_card_as::<char>(), Ok(['H', 'I', ' ', 'Z', '4', ' ']));
+//! ```
+//!
+//! Other code pages are selected by decoding into one of the code page types instead:
+//! - [`Ibm029`] for the IBM 029 keypunch code, same as [`char`]
+//! - [`Ibm026Commercial`] and [`Ibm026Fortran`] for the two character sets of the older IBM 026 keypunch
+//! - [`Ebcdic`] for the full EBCDIC card code, which covers all 256 byte values, and converts into a [`char`] using code page 037
+
+use crate::internal::{Bits, TryFromColumn};
+
+/// IBM 029 characters, indexed by zone punch (none, 12, 11, 0) and then digit punch (none, 1 through 9, 8-2 through 8-7), `'\0'` marks unused combinations
+#[rustfmt::skip]
+const IBM_029: [[char; 16]; 4] = [
+	[' ', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', '#', '@', '\'', '=', '"'],
+	['&', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', '¢', '.', '<', '(', '+', '|'],
+	['-', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '!', '$', '*', ')', ';', '¬'],
+	['0', '/', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '\0', ',', '%', '_', '>', '?'],
+];
+
+/// IBM 026 commercial characters, laid out like [`IBM_029`]
+#[rustfmt::skip]
+const IBM_026_COMMERCIAL: [[char; 16]; 4] = [
+	[' ', '1', '2', '3', '4', '5', '6', '7', '8', '9', '\0', '#', '@', '\0', '\0', '\0'],
+	['&', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', '\0', '.', '⌑', '\0', '\0', '\0'],
+	['-', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '\0', '$', '*', '\0', '\0', '\0'],
+	['0', '/', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '\0', ',', '%', '\0', '\0', '\0'],
+];
+
+/// IBM 026 FORTRAN characters, laid out like [`IBM_029`]
+#[rustfmt::skip]
+const IBM_026_FORTRAN: [[char; 16]; 4] = [
+	[' ', '1', '2', '3', '4', '5', '6', '7', '8', '9', '\0', '=', '\'', '\0', '\0', '\0'],
+	['+', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', '\0', '.', ')', '\0', '\0', '\0'],
+	['-', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '\0', '$', '*', '\0', '\0', '\0'],
+	['0', '/', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '\0', ',', '(', '\0', '\0', '\0'],
+];
+
+/// EBCDIC code page 037, indexed by byte
+#[rustfmt::skip]
+const CODE_PAGE_037: [char; 256] = [
+	'\u{0}', '\u{1}', '\u{2}', '\u{3}', '\u{9c}', '\u{9}', '\u{86}', '\u{7f}', '\u{97}', '\u{8d}', '\u{8e}', '\u{b}', '\u{c}', '\u{d}', '\u{e}', '\u{f}',
+	'\u{10}', '\u{11}', '\u{12}', '\u{13}', '\u{9d}', '\u{85}', '\u{8}', '\u{87}', '\u{18}', '\u{19}', '\u{92}', '\u{8f}', '\u{1c}', '\u{1d}', '\u{1e}', '\u{1f}',
+	'\u{80}', '\u{81}', '\u{82}', '\u{83}', '\u{84}', '\u{a}', '\u{17}', '\u{1b}', '\u{88}', '\u{89}', '\u{8a}', '\u{8b}', '\u{8c}', '\u{5}', '\u{6}', '\u{7}',
+	'\u{90}', '\u{91}', '\u{16}', '\u{93}', '\u{94}', '\u{95}', '\u{96}', '\u{4}', '\u{98}', '\u{99}', '\u{9a}', '\u{9b}', '\u{14}', '\u{15}', '\u{9e}', '\u{1a}',
+	' ', '\u{a0}', 'â', 'ä', 'à', 'á', 'ã', 'å', 'ç', 'ñ', '¢', '.', '<', '(', '+', '|',
+	'&', 'é', 'ê', 'ë', 'è', 'í', 'î', 'ï', 'ì', 'ß', '!', '$', '*', ')', ';', '¬',
+	'-', '/', 'Â', 'Ä', 'À', 'Á', 'Ã', 'Å', 'Ç', 'Ñ', '¦', ',', '%', '_', '>', '?',
+	'ø', 'É', 'Ê', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', '`', ':', '#', '@', '\'', '=', '"',
+	'Ø', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', '«', '»', 'ð', 'ý', 'þ', '±',
+	'°', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 'ª', 'º', 'æ', '¸', 'Æ', '¤',
+	'µ', '~', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '¡', '¿', 'Ð', 'Ý', 'Þ', '®',
+	'^', '£', '¥', '·', '©', '§', '¶', '¼', '½', '¾', '[', ']', '¯', '¨', '´', '×',
+	'{', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', '\u{ad}', 'ô', 'ö', 'ò', 'ó', 'õ',
+	'}', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '¹', 'û', 'ü', 'ù', 'ú', 'ÿ',
+	'\\', '÷', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '²', 'Ô', 'Ö', 'Ò', 'Ó', 'Õ',
+	'0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '³', 'Û', 'Ü', 'Ù', 'Ú', '\u{9f}',
+];
+
+/// Digit line 8 in a column
+const ROW_8: u16 = 1 << 1;
+
+/// Splits a column into its zone punch and digit punch, as indices into a code table.
+///
+/// Fails if more than one zone is punched or if the digit punches aren't a single digit or 8 plus another digit.
+const fn split(bits: Bits<12>) -> Option<(usize, usize)> {
+	let bits = bits.to_u128() as u16;
+	let zone = match bits >> 9 {
+		0b000 => 0,
+		0b100 => 1,
+		0b010 => 2,
+		0b001 => 3,
+		_ => return None,
+	};
+	// line 9 is the lowest bit, line 1 the highest
+	let digits = bits & 0x1ff;
+	let digit = if digits == 0 {
+		0
+	} else if digits.is_power_of_two() {
+		9 - digits.trailing_zeros() as usize
+	} else {
+		// 8 plus one of 2 through 7, which come after 1 through 9 in the table
+		let other = digits & !ROW_8;
+		let row = 9 - other.trailing_zeros() as usize;
+		if digits & ROW_8 == 0 || !other.is_power_of_two() || row < 2 || row > 7 {
+			return None;
+		}
+		row + 8
+	};
+	Some((zone, digit))
+}
+
+/// Looks up a column in a code table, returns [`None`] for punch combinations the table doesn't have.
+const fn decode_table(table: &[[char; 16]; 4], bits: Bits<12>) -> Option<char> {
+	match split(bits) {
+		Some((zone, digit)) if table[zone][digit] != '\0' => Some(table[zone][digit]),
+		_ => None,
+	}
+}
+
+/// Decodes one column using the IBM 029 keypunch code, returns [`None`] for punch combinations the 029 can't produce.
+pub const fn decode_029(bits: Bits<12>) -> Option<char> {
+	decode_table(&IBM_029, bits)
+}
+
+/// Hollerith columns decode into characters using the IBM 029 keypunch code.
+impl TryFromColumn<Bits<12>> for char {
+	#[inline]
+	fn try_from_column(bits: Bits<12>) -> Option<Self> {
+		decode_029(bits)
+	}
+}
+
+macro_rules! code_page {
+	($($(#[$meta:meta])* $name:ident => $table:ident,)*) => {$(
+		$(#[$meta])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+		pub struct $name(pub char);
+		impl TryFromColumn<Bits<12>> for $name {
+			#[inline]
+			fn try_from_column(bits: Bits<12>) -> Option<Self> {
+				match decode_table(&$table, bits) {
+					Some(c) => Some(Self(c)),
+					None => None,
+				}
+			}
+		}
+		impl From<$name> for char {
+			#[inline]
+			fn from(value: $name) -> Self {
+				value.0
+			}
+		}
+	)*};
+}
+code_page! {
+	/// A character decoded using the IBM 029 keypunch code.
+	Ibm029 => IBM_029,
+	/// A character decoded using the commercial character set of the IBM 026 keypunch.
+	Ibm026Commercial => IBM_026_COMMERCIAL,
+	/// A character decoded using the FORTRAN character set of the IBM 026 keypunch.
+	Ibm026Fortran => IBM_026_FORTRAN,
+}
+
+/// Zone punches for EBCDIC, as 12 / 11 / 0 / 9 line bits
+const Z12: u16 = 1 << 11;
+const Z11: u16 = 1 << 10;
+const Z0: u16 = 1 << 9;
+const Z9: u16 = 1 << 0;
+/// The single zones, indexed by the low two bits of the high nibble
+const SINGLE_ZONES: [u16; 4] = [Z12, Z11, Z0, 0];
+/// The double zones, indexed by the low two bits of the high nibble
+const DOUBLE_ZONES: [u16; 4] = [Z12 | Z0, Z12 | Z11, Z11 | Z0, Z12 | Z11 | Z0];
+
+/// Digit punches for a low nibble, 1 through 9 are single punches and 10 through 15 are 8-2 through 8-7
+const fn digit(nibble: u8) -> u16 {
+	match nibble {
+		0 => 0,
+		1..=9 => 1 << (9 - nibble),
+		_ => eight(nibble),
+	}
+}
+
+/// Digit punches for a low nibble from 9 to 15 in the rows that use 8-1 through 8-7
+const fn eight(nibble: u8) -> u16 {
+	ROW_8 | 1 << (9 - (nibble - 8))
+}
+
+/// The punches for an EBCDIC byte, following the standard IBM card code
+const fn ebcdic_punches(byte: u8) -> u16 {
+	let (quadrant, zone, nibble) = (byte >> 6, (byte >> 4 & 3) as usize, byte & 0xf);
+	let (single, double) = (SINGLE_ZONES[zone], DOUBLE_ZONES[zone]);
+	match (byte, quadrant, nibble) {
+		// these two are swapped to keep 0-1 as `/`, like on older keypunches
+		(0x61, ..) => Z0 | digit(1),
+		(0xe1, ..) => Z11 | Z0 | Z9 | digit(1),
+		(0x6a, ..) => Z12 | Z11,
+		(_, 0, 0) => double | Z9 | eight(9),
+		(_, 0, 1..=8) => single | Z9 | digit(nibble),
+		(_, 0, _) => single | Z9 | eight(nibble),
+		(_, 1, 0) => [0, Z12, Z11, Z12 | Z11 | Z0][zone],
+		(_, 1, 1..=8) => double | Z9 | digit(nibble),
+		(_, 1, _) => single | eight(nibble),
+		(_, 2, 0) => double | eight(9),
+		(_, 2, _) => double | digit(nibble),
+		(_, _, 0) => [Z12 | Z0, Z11 | Z0, Z0 | eight(10), Z0][zone],
+		(_, _, 1..=9) => single | digit(nibble),
+		(_, _, _) => double | Z9 | eight(nibble),
+	}
+}
+
+/// The punches for every EBCDIC byte, indexed by byte
+const EBCDIC_PUNCHES: [u16; 256] = {
+	let mut out = [0; 256];
+	let mut byte = 0;
+	while byte < 256 {
+		out[byte] = ebcdic_punches(byte as u8);
+		byte += 1;
+	}
+	out
+};
+
+/// A byte decoded using the EBCDIC card code, which has a punch combination for all 256 values.
+///
+/// Converts into a [`char`] using EBCDIC code page 037.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ebcdic(pub u8);
+impl Ebcdic {
+	/// Decodes one column using the EBCDIC card code, returns [`None`] for punch combinations without a byte.
+	pub const fn decode(bits: Bits<12>) -> Option<Self> {
+		let bits = bits.to_u128() as u16;
+		let mut byte = 0;
+		while byte < 256 {
+			if EBCDIC_PUNCHES[byte] == bits {
+				return Some(Self(byte as u8));
+			}
+			byte += 1;
+		}
+		None
+	}
+	/// The punches for this byte, the inverse of [`decode`](Ebcdic::decode).
+	pub const fn to_bits(self) -> Bits<12> {
+		Bits::new(EBCDIC_PUNCHES[self.0 as usize] as u128)
+	}
+}
+impl TryFromColumn<Bits<12>> for Ebcdic {
+	#[inline]
+	fn try_from_column(bits: Bits<12>) -> Option<Self> {
+		Self::decode(bits)
+	}
+}
+impl From<Ebcdic> for u8 {
+	#[inline]
+	fn from(value: Ebcdic) -> Self {
+		value.0
+	}
+}
+impl From<Ebcdic> for char {
+	#[inline]
+	fn from(value: Ebcdic) -> Self {
+		CODE_PAGE_037[value.0 as usize]
+	}
+}
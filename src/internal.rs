@@ -1,8 +1,19 @@
 // SPDX-License-Identifier: MIT
 //! Internal implementation stuff kept separate for some reason.
 
+use core::marker::PhantomData;
 use core::ops::{RangeFull, RangeTo, RangeToInclusive};
 
+use crate::{Endian, PunchCardError};
+
+/// Supertraits that keep the card traits from being implemented outside of this crate
+mod sealed {
+	/// Implemented for the three kinds of range that make up a line
+	pub trait Line {}
+	/// Implemented for lines and tuples of lines
+	pub trait Card {}
+}
+
 /// A single line (or tail of a line) in a punched card.
 ///
 /// Lines are typed like a linked list, for example the type of the tape in the `valid_u1_1` test is:
@@ -19,147 +30,815 @@ use core::ops::{RangeFull, RangeTo, RangeToInclusive};
 /// ..           RangeFull>>>>>>>>>
 /// ```
 ///
-pub trait PunchCardLine {
+/// Sealed, only the ranges of card source implement it.
+pub trait PunchCardLine: sealed::Line {
 	/// Head item / car of the line, [`true`](bool) for [`..=`](RangeToInclusive), [`false`](bool) for [`..=`](RangeTo)
 	const HEAD: Option<bool>;
 	/// Tail / cdr of the line
 	type Tail: PunchCardLine;
 	/// The amount of remaining items in this line.
 	const LENGTH: usize;
+	/// The remaining items as a bitmask with the first item as the most significant bit, fails to evaluate for lines longer than 128 items.
+	const BITS: u128;
+	/// The amount of remaining items as a type, see [`Len`]
+	type Length: ArrayLength;
 }
 
 /// Inner punch card type with more things
 /// stored as a parallel linked list
-pub trait PunchCardInner {
-	/// Length of the first line, it's guaranteed to be not equal to all the other lines, otherwise parsing will fail
+///
+/// Sealed, only lines and tuples of lines implement it.
+pub trait PunchCardInner: sealed::Card {
+	/// Length of the first line, all the other lines have to be the same length, otherwise the card fails to compile
 	const LENGTH: usize;
-	/// The output type of one entry, usually an integer of some kind.
-	type Output: Copy + Default;
-	/// Evaluates this section of the card and appends the value onto the output.
-	fn eval_part<const N: usize>(v: &mut [Self::Output; N], i: usize);
+	/// Evaluating this fails if the lines aren't all the same length, which catches the lines longer than 1024 items that [`LinesMatch`] can't tell apart
+	///
+	/// Only fails `cargo build`, not `cargo check`. Only the whole card is checked, by the methods of [`PunchCard`](crate::PunchCard), as the rest of the card is then the same length too.
+	const LENGTHS_MATCH: () = ();
+	/// Amount of lines in the card
+	const HEIGHT: usize;
+	/// The bits of one column, always [`Bits<height>`](Bits)
+	type Column: Copy + Default + FromColumn<Self::Column>;
+	/// The default output type of one entry, the smallest integer that fits a column (or [`bool`] for single-line cards)
+	type Output: Copy + Default + FromColumn<Self::Column>;
+	/// [`LENGTH`](PunchCardInner::LENGTH) as a type, see [`Len`]
+	type Length: ArrayLength;
+	/// [`HEIGHT`](PunchCardInner::HEIGHT) as a type, see [`Len`]
+	type Height: ArrayLength;
+	/// Evaluates this section of the card and appends the value onto the output, without checking [`LENGTHS_MATCH`](PunchCardInner::LENGTHS_MATCH).
+	fn eval_part<O: FromColumn<Self::Column>>(v: &mut [O], i: usize);
+	/// Like [`eval_part`](PunchCardInner::eval_part), but reports mismatched lines as an error instead of failing to compile.
+	fn try_eval_part<O: FromColumn<Self::Column>>(
+		v: &mut [O],
+		i: usize,
+	) -> Result<(), PunchCardError> {
+		Self::eval_part(v, i);
+		Ok(())
+	}
+	/// Evaluates every line of the card into the output, one entry per line.
+	fn eval_rows<O: FromRow>(v: &mut [O]);
+	/// Evaluates only column `i` of this section of the card, walking the tails up to it, without checking [`LENGTHS_MATCH`](PunchCardInner::LENGTHS_MATCH).
+	fn eval_column<O: FromColumn<Self::Column>>(i: usize) -> Option<O>;
+	/// The packed bits of the first column of this section, like [`Bits::to_u128`], zero once the lines end
+	const HEADS: u128;
+	/// This section of the card without its first column
+	type Tails: PunchCardInner;
+	/// The whole card decoded at compile time, padded with zeroes to [`CONST_LENGTH`] columns, used by [`punch_card_const`](crate::punch_card_const)
+	const DECODED: [Self::Output; CONST_LENGTH];
+}
+
+/// Implemented by cards whose lines are all as long as the first, so mismatched cards fail `cargo check` and not just `cargo build`.
+///
+/// Each line is compared against the first one by [`SameLength`], which names the first line that doesn't match.
+/// Lines longer than 1024 items all have the same [`Len`], those are only compared by [`LENGTHS_MATCH`](PunchCardInner::LENGTHS_MATCH).
+pub trait LinesMatch: sealed::Card {}
+/// Implemented by pairs of equal lengths, line `ROW` of a card being compared against the first line by [`LinesMatch`]
+#[diagnostic::on_unimplemented(
+	message = "row {ROW} of the card isn't the same length as row 0",
+	label = "every line of the card has to be as long as the first"
+)]
+pub trait SameLength<const ROW: usize> {}
+impl<const N: usize, const ROW: usize> SameLength<ROW> for (Len<N>, Len<N>) {}
+
+/// The longest line that can be decoded at compile time, see [`punch_card_const`](crate::punch_card_const)
+pub const CONST_LENGTH: usize = 1024;
+/// Writes the packed bits of every column of card `C` into `out`, starting at index `i`
+const fn const_columns<C: PunchCardInner>(out: &mut [u128; CONST_LENGTH], i: usize) {
+	if C::LENGTH > 0 {
+		out[i] = C::HEADS;
+		const_columns::<C::Tails>(out, i + 1);
+	}
+}
+/// The packed bits of every column of card `C`, padded with zeroes
+const fn const_card<C: PunchCardInner>() -> [u128; CONST_LENGTH] {
+	assert!(
+		C::LENGTH <= CONST_LENGTH,
+		"lines longer than 1024 items can't be decoded at compile time"
+	);
+	let mut out = [0; CONST_LENGTH];
+	const_columns::<C>(&mut out, 0);
+	out
+}
+/// Converts packed column bits into an output type at compile time
+macro_rules! const_cast {
+	(bool, $bits:expr) => {
+		$bits != 0
+	};
+	($out_type:ident, $bits:expr) => {
+		$bits as $out_type
+	};
+}
+/// Converts the result of [`const_card`] into an output type at compile time
+macro_rules! const_decode {
+	($out_type:ident, $card:ty) => {{
+		let bits = const_card::<$card>();
+		let mut out = [const_cast!($out_type, 0u128); CONST_LENGTH];
+		let mut i = 0;
+		while i < CONST_LENGTH {
+			out[i] = const_cast!($out_type, bits[i]);
+			i += 1;
+		}
+		out
+	}};
+}
+
+/// Fails to evaluate if card `C` doesn't have exactly `N` columns
+struct DecodedFits<C, const N: usize>(PhantomData<C>);
+impl<C: PunchCardInner, const N: usize> DecodedFits<C, N> {
+	const OK: () = assert!(C::LENGTH == N, "the output length doesn't match the card");
+}
+/// The first `N` columns of [`PunchCardInner::DECODED`], used by [`punch_card_const`](crate::punch_card_const)
+pub(crate) const fn decoded<C: PunchCardInner, const N: usize>() -> [C::Output; N] {
+	let () = DecodedFits::<C, N>::OK;
+	let decoded = C::DECODED;
+	let mut out = [decoded[0]; N];
+	let mut i = 0;
+	while i < N {
+		out[i] = decoded[i];
+		i += 1;
+	}
+	out
+}
+
+/// Arrays of any length, so outputs can be built from a [`Len`] without `generic_const_exprs`.
+pub trait Array: Sized {
+	/// The type of each entry
+	type Item;
+	/// Builds the array from a function of each index, like [`core::array::from_fn`].
+	fn from_fn(f: impl FnMut(usize) -> Self::Item) -> Self;
+	/// The entries of the array.
+	fn as_slice(&self) -> &[Self::Item];
+	/// The entries of the array, mutably.
+	fn as_mut_slice(&mut self) -> &mut [Self::Item];
+}
+impl<T, const N: usize> Array for [T; N] {
+	type Item = T;
+	#[inline(always)]
+	fn from_fn(f: impl FnMut(usize) -> T) -> Self {
+		core::array::from_fn(f)
+	}
+	#[inline(always)]
+	fn as_slice(&self) -> &[T] {
+		self
+	}
+	#[inline(always)]
+	fn as_mut_slice(&mut self) -> &mut [T] {
+		self
+	}
+}
+
+/// An array length as a type, which lines count up one item at a time.
+///
+/// Implemented up to `Len<1024>`, `Len<1025>` stands for every longer length, cards with lines that long need the `nightly` feature.
+pub struct Len<const N: usize>;
+/// A length that arrays can be built from, implemented by [`Len`].
+pub trait ArrayLength {
+	/// The length one item longer
+	type Next: ArrayLength;
+	/// An array of this length
+	type Array<U>: Array<Item = U>;
+}
+/// Implements [`ArrayLength`] for `Len<0>` through `Len<4 ^ digits>`, one base-4 digit at a time
+macro_rules! array_length_impl {
+	($n:expr;) => {
+		impl ArrayLength for Len<{ $n }> {
+			type Next = Len<{ $n + 1 }>;
+			type Array<U> = [U; $n];
+		}
+	};
+	($n:expr; x $($rest:tt)*) => {
+		array_length_impl!($n * 4; $($rest)*);
+		array_length_impl!($n * 4 + 1; $($rest)*);
+		array_length_impl!($n * 4 + 2; $($rest)*);
+		array_length_impl!($n * 4 + 3; $($rest)*);
+	};
+}
+array_length_impl!(0; x x x x x);
+array_length_impl!(1024;);
+impl ArrayLength for Len<1025> {
+	type Next = Self;
+	type Array<U> = TooLong<U>;
+}
+
+/// The output of a card with lines longer than 1024 items, which fails to compile
+pub struct TooLong<U>(PhantomData<U>);
+impl<U> TooLong<U> {
+	const FAIL: () = panic!("lines longer than 1024 items need the `nightly` feature");
+}
+impl<U> Array for TooLong<U> {
+	type Item = U;
+	fn from_fn(_: impl FnMut(usize) -> U) -> Self {
+		let () = Self::FAIL;
+		Self(PhantomData)
+	}
+	fn as_slice(&self) -> &[U] {
+		&[]
+	}
+	fn as_mut_slice(&mut self) -> &mut [U] {
+		&mut []
+	}
+}
+
+/// Fails to evaluate if a column of height `H` doesn't fit into `W` bits
+struct Fits<const H: usize, const W: usize>;
+impl<const H: usize, const W: usize> Fits<H, W> {
+	const OK: () = assert!(H <= W, "column doesn't fit into the output type");
+}
+
+/// The bits of one column of a card of height `H`, packed with the top line as the most significant bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bits<const H: usize>(u128);
+impl<const H: usize> Bits<H> {
+	/// Packs the bits of a column, only the lowest `H` bits are kept.
+	#[inline(always)]
+	pub const fn new(bits: u128) -> Self {
+		let () = Fits::<H, 128>::OK;
+		if H == 0 {
+			Self(0)
+		} else {
+			Self(bits & u128::MAX >> (128 - H))
+		}
+	}
+	/// Whether the hole in line `row` (counting from the top) is punched.
+	#[inline(always)]
+	pub const fn get(self, row: usize) -> bool {
+		row < H && self.0 >> (H - 1 - row) & 1 == 1
+	}
+	/// The packed bits, with the bottom line as the least significant bit.
+	#[inline(always)]
+	pub const fn to_u128(self) -> u128 {
+		self.0
+	}
+	/// The bits from top to bottom.
+	pub fn to_array(self) -> [bool; H] {
+		core::array::from_fn(|row| self.get(row))
+	}
+	/// The column upside down, with the top line as the least significant bit.
+	#[inline(always)]
+	pub const fn reverse(self) -> Self {
+		if H == 0 {
+			self
+		} else {
+			Self(self.0.reverse_bits() >> (128 - H))
+		}
+	}
+}
+
+/// Conversion from the bits of one column into an output type, used by [`punch_card_as`](crate::PunchCard::punch_card_as).
+///
+/// Implemented for:
+/// - [`bool`] from single-line columns
+/// - `[bool; H]` from columns of height `H`
+/// - unsigned integers from columns that fit into them, with the top line as the most significant bit
+/// - signed integers from columns that fit into them, read as two's complement with the top line as the sign bit
+/// - [`f32`] and [`f64`] from columns of exactly 32 and 64 lines, read as IEEE 754 bits like [`u32`] and [`u64`]
+/// - [`LsbFirst`] of any of these, reading the column upside down
+///
+/// Types that not every column is valid for implement [`TryFromColumn`] or [`FromPunchBits`] instead.
+pub trait FromColumn<C>: Sized {
+	/// Converts one column of the card.
+	fn from_column(bits: C) -> Self;
+}
+/// Fallible conversion from the bits of one column into an output type, used by [`try_punch_card_as`](crate::PunchCard::try_punch_card_as).
+///
+/// Implemented for every [`FromColumn`] type of this crate, every [`FromPunchBits`] type, [`char`] from [Hollerith](crate::hollerith) columns, and the [`parity`](crate::parity) types.
+pub trait TryFromColumn<C>: Sized {
+	/// Converts one column of the card, returning [`None`] if it isn't a valid value.
+	fn try_from_column(bits: C) -> Option<Self>;
+}
+/// Conversion from a column of any height into your own type, with your own validation, used by [`try_punch_card_as`](crate::PunchCard::try_punch_card_as).
+///
+/// Implementing this is the easiest way to read columns as newtypes, enums, or flags, without a [`TryFromColumn`] impl for every height:
+///
+/// ```rust
+/// use punch_card::internal::FromPunchBits;
+/// use punch_card::{PunchCard, PunchCardError};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Light {
+///     Off,
+///     Red,
+///     Green,
+/// }
+/// impl FromPunchBits for Light {
+///     fn from_punch_bits(height: usize, bits: u128) -> Option<Self> {
+///         match (height, bits) {
+///             (2, 0b00) => Some(Self::Off),
+///             (2, 0b10) => Some(Self::Red),
+///             (2, 0b01) => Some(Self::Green),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// #[rustfmt::skip]
+/// let card = (
+///     .. ..=.. ..=..,
+///     .. .. ..=..=..,
+/// );
+/// assert_eq!(
+///     card.try_punch_card_as::<Light>(),
+///     Err(PunchCardError::InvalidColumn { column: 3 }),
+/// );
+/// ```
+pub trait FromPunchBits: Sized {
+	/// Converts a column of `height` lines, packed like [`Bits`] with the top line as the most significant bit, returning [`None`] if it isn't a valid value.
+	fn from_punch_bits(height: usize, bits: u128) -> Option<Self>;
+}
+impl<const H: usize, T: FromPunchBits> TryFromColumn<Bits<H>> for T {
+	#[inline(always)]
+	fn try_from_column(bits: Bits<H>) -> Option<Self> {
+		T::from_punch_bits(H, bits.0)
+	}
+}
+
+impl<const H: usize> FromColumn<Bits<H>> for Bits<H> {
+	#[inline(always)]
+	fn from_column(bits: Bits<H>) -> Self {
+		bits
+	}
+}
+impl FromColumn<Bits<1>> for bool {
+	#[inline(always)]
+	fn from_column(bits: Bits<1>) -> Self {
+		bits.0 == 1
+	}
+}
+impl<const H: usize> FromColumn<Bits<H>> for [bool; H] {
+	#[inline(always)]
+	fn from_column(bits: Bits<H>) -> Self {
+		bits.to_array()
+	}
+}
+macro_rules! from_column_int {
+	($($unsigned:ty, $signed:ty;)*) => {$(
+		impl<const H: usize> FromColumn<Bits<H>> for $unsigned {
+			#[inline(always)]
+			fn from_column(bits: Bits<H>) -> Self {
+				let () = Fits::<H, { <$unsigned>::BITS as usize }>::OK;
+				bits.0 as $unsigned
+			}
+		}
+		impl<const H: usize> FromColumn<Bits<H>> for $signed {
+			#[inline(always)]
+			fn from_column(bits: Bits<H>) -> Self {
+				let () = Fits::<H, { <$signed>::BITS as usize }>::OK;
+				if H == 0 {
+					0
+				} else {
+					// shifting the top line into the sign bit and back sign-extends it
+					((bits.0 << (128 - H)) as i128 >> (128 - H)) as $signed
+				}
+			}
+		}
+	)*};
 }
+from_column_int! {
+	u8, i8;
+	u16, i16;
+	u32, i32;
+	u64, i64;
+	u128, i128;
+}
+macro_rules! from_column_float {
+	($($float:ty, $height:literal;)*) => {$(
+		impl FromColumn<Bits<$height>> for $float {
+			#[inline(always)]
+			fn from_column(bits: Bits<$height>) -> Self {
+				<$float>::from_bits(bits.0 as _)
+			}
+		}
+	)*};
+}
+from_column_float! {
+	f32, 32;
+	f64, 64;
+}
+
+/// A value read with the top line as the least significant bit, used by [`punch_card_lsb`](crate::PunchCard::punch_card_lsb).
+///
+/// Works with every [`FromColumn`] type, like `punch_card_as::<LsbFirst<i8>>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LsbFirst<T>(pub T);
+impl<const H: usize, T: FromColumn<Bits<H>>> FromColumn<Bits<H>> for LsbFirst<T> {
+	#[inline(always)]
+	fn from_column(bits: Bits<H>) -> Self {
+		Self(T::from_column(bits.reverse()))
+	}
+}
+
+/// Implements [`TryFromColumn`] for [`FromColumn`] types, never failing
+macro_rules! try_from_column {
+	($($generics:tt $column:ty => $($out_type:ty),*;)*) => {$($(
+		try_from_column!(@impl $generics $column, $out_type);
+	)*)*};
+	(@impl [$($generics:tt)*] $column:ty, $out_type:ty) => {
+		impl<$($generics)*> TryFromColumn<$column> for $out_type {
+			#[inline(always)]
+			fn try_from_column(bits: $column) -> Option<Self> {
+				Some(Self::from_column(bits))
+			}
+		}
+	};
+}
+try_from_column! {
+	[const H: usize] Bits<H> => Bits<H>, [bool; H], u8, i8, u16, i16, u32, i32, u64, i64, u128, i128;
+	[const H: usize, T: FromColumn<Bits<H>>] Bits<H> => LsbFirst<T>;
+	[] Bits<1> => bool;
+	[] Bits<32> => f32;
+	[] Bits<64> => f64;
+}
+
+/// Packs the heads of some lines like [`Bits`], first line on top
+macro_rules! pack_heads {
+	($($in_type:ident),*) => {{
+		let mut bits = 0u128;
+		$(bits = bits << 1 | matches!($in_type::HEAD, Some(true)) as u128;)*
+		bits
+	}};
+}
+/// Conversion from one line of a card into an output type, used by [`punch_card_rows`](crate::PunchCard::punch_card_rows).
+///
+/// Implemented for unsigned integers, with the leftmost column as the most significant bit.
+pub trait FromRow: Copy {
+	/// The longest line that fits into this type
+	const WIDTH: usize;
+	/// Converts one line of the card, packed like [`PunchCardLine::BITS`].
+	fn from_row(bits: u128) -> Self;
+}
+macro_rules! from_row_int {
+	($($type:ty),*) => {$(
+		impl FromRow for $type {
+			const WIDTH: usize = <$type>::BITS as usize;
+			#[inline(always)]
+			fn from_row(bits: u128) -> Self {
+				bits as $type
+			}
+		}
+	)*};
+}
+from_row_int! {u8, u16, u32, u64, u128}
 
+/// Conversion from several bytes into one integer, used by [`punch_card_words`](crate::PunchCard::punch_card_words).
+///
+/// Implemented for the integers wider than a byte.
+#[diagnostic::on_unimplemented(message = "`{Self}` isn't an integer wider than a byte")]
+pub trait FromBytes: Copy {
+	/// The amount of bytes, and so columns, in one word
+	const BYTES: usize;
+	/// Whether the integer is signed, so narrower values are sign-extended
+	const SIGNED: bool;
+	/// Converts [`BYTES`](FromBytes::BYTES) bytes in the given byte order.
+	fn from_bytes(bytes: &[u8], endian: Endian) -> Self;
+}
+macro_rules! from_bytes_int {
+	($($type:ty),*) => {$(
+		impl FromBytes for $type {
+			const BYTES: usize = core::mem::size_of::<$type>();
+			const SIGNED: bool = <$type>::MIN != 0;
+			#[inline(always)]
+			fn from_bytes(bytes: &[u8], endian: Endian) -> Self {
+				let bytes = bytes.try_into().expect("one word of bytes");
+				match endian {
+					Endian::Little => <$type>::from_le_bytes(bytes),
+					Endian::Big => <$type>::from_be_bytes(bytes),
+				}
+			}
+		}
+	)*};
+}
+from_bytes_int! {u16, u32, u64, u128, i16, i32, i64, i128}
+/// Reads an integer from fewer bytes than it has, used by `#[punch(bytes = N)]` fields of [`FromPunchCard`] structs.
+///
+/// The missing most significant bytes are zeroes, or copies of the sign bit for signed integers.
+#[doc(hidden)]
+pub fn from_narrow_bytes<T: FromBytes>(bytes: &[u8], endian: Endian) -> T {
+	let top = match endian {
+		Endian::Little => bytes.last(),
+		Endian::Big => bytes.first(),
+	};
+	let fill = match top {
+		Some(&top) if T::SIGNED && top & 0x80 != 0 => 0xFF,
+		_ => 0,
+	};
+	let mut word = [fill; 16];
+	let word = &mut word[..T::BYTES];
+	match endian {
+		Endian::Little => word[..bytes.len()].copy_from_slice(bytes),
+		Endian::Big => word[T::BYTES - bytes.len()..].copy_from_slice(bytes),
+	}
+	T::from_bytes(word, endian)
+}
+
+/// Types read from the byte columns of a card, used by [`punch_card_decode`](crate::PunchCard::punch_card_decode).
+///
+/// Implemented for integers, [`bool`] from a byte that's 0 or 1, and arrays of any of these.
+/// Structs and enums implement it with `#[derive(FromPunchCard)]` from the `macros` feature.
+pub trait FromPunchCard: Sized {
+	/// The amount of byte columns
+	const BYTES: usize;
+	/// Reads [`BYTES`](FromPunchCard::BYTES) bytes, with `endian` as the byte order of integers that don't pick their own.
+	///
+	/// Returns the index of the first column of an invalid value on failure.
+	fn from_punch_bytes(bytes: &[u8], endian: Endian) -> Result<Self, usize>;
+}
+macro_rules! from_punch_card_int {
+	($($type:ty),*) => {$(
+		impl FromPunchCard for $type {
+			const BYTES: usize = core::mem::size_of::<$type>();
+			#[inline(always)]
+			fn from_punch_bytes(bytes: &[u8], endian: Endian) -> Result<Self, usize> {
+				let bytes = bytes.try_into().expect("one value of bytes");
+				Ok(match endian {
+					Endian::Little => <$type>::from_le_bytes(bytes),
+					Endian::Big => <$type>::from_be_bytes(bytes),
+				})
+			}
+		}
+	)*};
+}
+from_punch_card_int! {u8, i8, u16, i16, u32, i32, u64, i64, u128, i128}
+impl FromPunchCard for bool {
+	const BYTES: usize = 1;
+	#[inline(always)]
+	fn from_punch_bytes(bytes: &[u8], _: Endian) -> Result<Self, usize> {
+		match bytes {
+			[0] => Ok(false),
+			[1] => Ok(true),
+			_ => Err(0),
+		}
+	}
+}
+impl<T: FromPunchCard, const N: usize> FromPunchCard for [T; N] {
+	const BYTES: usize = T::BYTES * N;
+	fn from_punch_bytes(bytes: &[u8], endian: Endian) -> Result<Self, usize> {
+		let mut error = None;
+		let out: [Option<T>; N] = core::array::from_fn(|i| {
+			let start = i * T::BYTES;
+			match T::from_punch_bytes(&bytes[start..start + T::BYTES], endian) {
+				Ok(value) if error.is_none() => Some(value),
+				Ok(_) => None,
+				Err(column) => {
+					error.get_or_insert(start + column);
+					None
+				}
+			}
+		});
+		match error {
+			Some(column) => Err(column),
+			None => Ok(out.map(|value| value.expect("checked above"))),
+		}
+	}
+}
+
+/// Fails to evaluate if card `C` isn't exactly as long as `T`
+pub(crate) struct DecodeFits<C, T>(PhantomData<(C, T)>);
+impl<C: PunchCardInner, T: FromPunchCard> DecodeFits<C, T> {
+	pub(crate) const OK: () = assert!(
+		C::LENGTH == T::BYTES,
+		"the card isn't as long as the output type"
+	);
+}
+
+/// Fails to evaluate if card `C` doesn't fit into a buffer of [`CONST_LENGTH`] bytes
+pub(crate) struct BytesFit<C>(PhantomData<C>);
+impl<C: PunchCardInner> BytesFit<C> {
+	pub(crate) const OK: () = assert!(
+		C::LENGTH <= CONST_LENGTH,
+		"cards longer than 1024 columns can't be read as bytes"
+	);
+}
+
+/// Fails to evaluate if card `C` doesn't split into exactly `N` words of `W`
+pub(crate) struct WordsFit<C, W, const N: usize>(PhantomData<(C, W)>);
+impl<C: PunchCardInner, W: FromBytes, const N: usize> WordsFit<C, W, N> {
+	pub(crate) const OK: () = assert!(
+		C::LENGTH == N * W::BYTES,
+		"the card doesn't split into the output amount of words"
+	);
+}
+
+/// Fails to evaluate if card `C` doesn't pack into exactly `N` bytes
+pub(crate) struct PackedFits<C, const N: usize>(PhantomData<C>);
+impl<C: PunchCardInner, const N: usize> PackedFits<C, N> {
+	pub(crate) const OK: () = assert!(
+		C::LENGTH.div_ceil(8) == N,
+		"the card doesn't pack into the output amount of bytes"
+	);
+}
+/// Sets the bits of the punched holes of single-line card `C` in `out`, starting at column `i`
+#[inline(always)]
+fn pack_part<C: PunchCardInner>(out: &mut [u8], i: usize, lsb: bool) {
+	if C::LENGTH > 0 {
+		if C::HEADS != 0 {
+			out[i / 8] |= if lsb { 1 << (i % 8) } else { 0x80 >> (i % 8) };
+		}
+		pack_part::<C::Tails>(out, i + 1, lsb);
+	}
+}
+/// Packs the columns of a single-line card `C` into bytes, eight columns each, with the first column as the most or least significant bit
+pub(crate) fn packed<C: PunchCardInner, const N: usize>(lsb: bool) -> [u8; N]
+where
+	bool: FromColumn<C::Column>,
+{
+	let () = C::LENGTHS_MATCH;
+	let () = PackedFits::<C, N>::OK;
+	let mut out = [0; N];
+	pack_part::<C>(&mut out, 0, lsb);
+	out
+}
+
+/// Fails to evaluate if the lines of card `C` don't fit into `O`
+struct RowsFit<C, O>(PhantomData<(C, O)>);
+impl<C: PunchCardInner, O: FromRow> RowsFit<C, O> {
+	const OK: () = assert!(
+		C::LENGTH <= O::WIDTH,
+		"lines don't fit into the output type"
+	);
+}
+
+macro_rules! replace_unit {
+	($_:tt) => {
+		()
+	};
+}
 macro_rules! punch_card_impl {
-	(($($in_type:ident),* $(,)?), $first:ident, $eval:expr, $out_type:ty) => {
-		impl<$($in_type: PunchCardLine),*> PunchCardInner for ($($in_type),+,) {
+	($out_type:ident, $first:ident $(, $in_type:ident)*) => {
+		impl<$first: PunchCardLine, $($in_type: PunchCardLine),*> sealed::Card for ($first, $($in_type,)*) {}
+		impl<$first: PunchCardLine, $($in_type: PunchCardLine),*> PunchCardInner for ($first, $($in_type,)*) {
 			const LENGTH: usize = $first::LENGTH;
+			const LENGTHS_MATCH: () = assert!(true $(&& $in_type::LENGTH == $first::LENGTH)*, "mismatched tape lengths");
+			const HEIGHT: usize = [replace_unit!($first) $(, replace_unit!($in_type))*].len();
+			type Column = Bits<{ [replace_unit!($first) $(, replace_unit!($in_type))*].len() }>;
 			type Output = $out_type;
+			type Length = $first::Length;
+			type Height = Len<{ [replace_unit!($first) $(, replace_unit!($in_type))*].len() }>;
 			#[inline(always)]
 			#[track_caller]
-			// hidden since this isn't user-facing stuff
-			fn eval_part<const N: usize>(v: &mut [Self::Output; N], i: usize) {
-				// we use || here to keep going and catch any length mismatches
-				if $($in_type::LENGTH > 0)||* {
-					v[i] = $eval;
-					<($($in_type::Tail),*,)>::eval_part(v, i + 1);
+			fn eval_part<O: FromColumn<Self::Column>>(v: &mut [O], i: usize) {
+				if $first::LENGTH > 0 {
+					v[i] = O::from_column(Bits::new(Self::HEADS));
+					<($first::Tail, $($in_type::Tail,)*)>::eval_part(v, i + 1);
+				}
+			}
+			#[inline(always)]
+			fn try_eval_part<O: FromColumn<Self::Column>>(v: &mut [O], i: usize) -> Result<(), PunchCardError> {
+				// every line is compared against the first one
+				let heads = [$first::HEAD $(, $in_type::HEAD)*];
+				if let Some(row) = heads.iter().position(|head| head.is_some() != heads[0].is_some()) {
+					return Err(if heads[0].is_some() {
+						PunchCardError::RowTooShort { row, column: i }
+					} else {
+						PunchCardError::RowTooLong { row, column: i }
+					});
+				}
+				if $first::LENGTH > 0 {
+					v[i] = O::from_column(Bits::new(Self::HEADS));
+					<($first::Tail, $($in_type::Tail,)*)>::try_eval_part(v, i + 1)
+				} else {
+					Ok(())
+				}
+			}
+			const HEADS: u128 = pack_heads!($first $(, $in_type)*);
+			type Tails = ($first::Tail, $($in_type::Tail,)*);
+			const DECODED: [$out_type; CONST_LENGTH] = {
+				let () = Self::LENGTHS_MATCH;
+				const_decode!($out_type, Self)
+			};
+			#[inline(always)]
+			fn eval_column<O: FromColumn<Self::Column>>(i: usize) -> Option<O> {
+				if $first::LENGTH == 0 {
+					None
+				} else if i == 0 {
+					Some(O::from_column(Bits::new(Self::HEADS)))
+				} else {
+					<($first::Tail, $($in_type::Tail,)*)>::eval_column(i - 1)
+				}
+			}
+			#[inline(always)]
+			fn eval_rows<O: FromRow>(v: &mut [O]) {
+				let () = Self::LENGTHS_MATCH;
+				let () = RowsFit::<Self, O>::OK;
+				for (out, bits) in v.iter_mut().zip([$first::BITS $(, $in_type::BITS)*]) {
+					*out = O::from_row(bits);
 				}
 			}
 		}
 	};
 }
-macro_rules! punch_card_impl_int {
-	($res:ty, ($first:ident, $($typ:ident => $offset:expr),* $(,)?)) => {
-		punch_card_impl!(($($typ),*), $first, $((if <$typ>::HEAD.expect("mismatched tape lengths") {1 << $offset} else {0}))|*, $res);
+/// Implements [`PunchCardInner`] for every tuple of lines up to the full table, each line adding one bit to the column.
+///
+/// Each line takes the next number of the list in front as its row for [`LinesMatch`], a plain literal since rustdoc can't normalize a const expression in a where clause with `generic_const_exprs`.
+macro_rules! punch_card_impl_all {
+	([$($rows:tt)*] $(@[$($done:ident $done_row:tt)*])? $out_type:ident: ; $($rest:tt)*) => {
+		punch_card_impl_all!([$($rows)*] @[$($($done $done_row)*)?] $($rest)*);
+	};
+	([$row:tt $($rows:tt)*] $(@[$($done:ident $done_row:tt)*])? $out_type:ident: $next:ident $($more:ident)*; $($rest:tt)*) => {
+		punch_card_impl!($out_type, $($($done,)*)? $next);
+		lines_match_impl!($($($done $done_row)*)? $next $row);
+		punch_card_impl_all!(
+			[$($rows)*] @[$($($done $done_row)*)? $next $row]
+			$out_type: $($more)*; $($rest)*
+		);
+	};
+	([$($rows:tt)*] @[$($done:ident $done_row:tt)*]) => {};
+}
+/// Implements [`LinesMatch`] for a tuple of lines, comparing the [`Len`] of every line after the first with the first
+macro_rules! lines_match_impl {
+	($first:ident 0 $($in_type:ident $row:tt)*) => {
+		impl<$first: PunchCardLine, $($in_type: PunchCardLine),*> LinesMatch for ($first, $($in_type,)*)
+		where
+			$(($first::Length, $in_type::Length): SameLength<$row>,)*
+		{}
 	};
 }
+impl<T: PunchCardLine> sealed::Card for T {}
 impl<T: PunchCardLine> PunchCardInner for T {
 	const LENGTH: usize = T::LENGTH;
+	const HEIGHT: usize = 1;
+	type Column = Bits<1>;
 	type Output = bool;
+	type Length = T::Length;
+	type Height = Len<1>;
 
 	#[inline(always)]
 	#[track_caller]
-	fn eval_part<const N: usize>(v: &mut [Self::Output; N], i: usize) {
+	fn eval_part<O: FromColumn<Self::Column>>(v: &mut [O], i: usize) {
 		if T::LENGTH > 0 {
-			v[i] = T::HEAD.expect("mismatched tape lengths");
+			v[i] = O::from_column(Bits::new(Self::HEADS));
 			T::Tail::eval_part(v, i + 1);
 		}
 	}
+	const HEADS: u128 = pack_heads!(T);
+	type Tails = T::Tail;
+	const DECODED: [bool; CONST_LENGTH] = const_decode!(bool, Self);
+	#[inline(always)]
+	fn eval_column<O: FromColumn<Self::Column>>(i: usize) -> Option<O> {
+		if T::LENGTH == 0 {
+			None
+		} else if i == 0 {
+			Some(O::from_column(Bits::new(Self::HEADS)))
+		} else {
+			T::Tail::eval_column(i - 1)
+		}
+	}
+	#[inline(always)]
+	fn eval_rows<O: FromRow>(v: &mut [O]) {
+		let () = RowsFit::<Self, O>::OK;
+		v[0] = O::from_row(T::BITS);
+	}
+}
+punch_card_impl_all! {
+	[
+		0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+		16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+		32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47
+		48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63
+		64 65 66 67 68 69 70 71 72 73 74 75 76 77 78 79
+		80 81 82 83 84 85 86 87 88 89 90 91 92 93 94 95
+		96 97 98 99 100 101 102 103 104 105 106 107 108 109 110 111
+		112 113 114 115 116 117 118 119 120 121 122 123 124 125 126 127
+	]
+	bool: T00;
+	u8: T01 T02 T03 T04 T05 T06 T07;
+	u16: T08 T09 T0a T0b T0c T0d T0e T0f;
+	u32:
+		T10 T11 T12 T13 T14 T15 T16 T17 T18 T19 T1a T1b T1c T1d T1e T1f;
+	u64:
+		T20 T21 T22 T23 T24 T25 T26 T27 T28 T29 T2a T2b T2c T2d T2e T2f
+		T30 T31 T32 T33 T34 T35 T36 T37 T38 T39 T3a T3b T3c T3d T3e T3f;
+	u128:
+		T40 T41 T42 T43 T44 T45 T46 T47 T48 T49 T4a T4b T4c T4d T4e T4f
+		T50 T51 T52 T53 T54 T55 T56 T57 T58 T59 T5a T5b T5c T5d T5e T5f
+		T60 T61 T62 T63 T64 T65 T66 T67 T68 T69 T6a T6b T6c T6d T6e T6f
+		T70 T71 T72 T73 T74 T75 T76 T77 T78 T79 T7a T7b T7c T7d T7e T7f;
 }
-punch_card_impl! {(T), T, T::HEAD.expect("mismatched tape lengths"), bool}
-punch_card_impl_int! {u8, (T0,
-	T0 => 7, T1 => 6, T2 => 5, T3 => 4, T4 => 3, T5 => 2, T6 => 1, T7 => 0,
-)}
-punch_card_impl_int! {u16, (T0,
-	T0 => 0xf, T1 => 0xe, T2 => 0xd, T3 => 0xc,
-	T4 => 0xb, T5 => 0xa, T6 => 0x9, T7 => 0x8,
-	T8 => 0x7, T9 => 0x6, Ta => 0x5, Tb => 0x4,
-	Tc => 0x3, Td => 0x2, Te => 0x1, Tf => 0x0,
-)}
-punch_card_impl_int! {u32, (T00,
-	T00 => 0x1f, T01 => 0x1e, T02 => 0x1d, T03 => 0x1c,
-	T04 => 0x1b, T05 => 0x1a, T06 => 0x19, T07 => 0x18,
-	T08 => 0x17, T09 => 0x16, T0a => 0x15, T0b => 0x14,
-	T0c => 0x13, T0d => 0x12, T0e => 0x11, T0f => 0x10,
-	T10 => 0x0f, T11 => 0x0e, T12 => 0x0d, T13 => 0x0c,
-	T14 => 0x0b, T15 => 0x0a, T16 => 0x09, T17 => 0x08,
-	T18 => 0x07, T19 => 0x06, T1a => 0x05, T1b => 0x04,
-	T1c => 0x03, T1d => 0x02, T1e => 0x01, T1f => 0x00,
-)}
-punch_card_impl_int! {u64, (T00,
-	T00 => 0x3f, T01 => 0x3e, T02 => 0x3d, T03 => 0x3c,
-	T04 => 0x3b, T05 => 0x3a, T06 => 0x39, T07 => 0x38,
-	T08 => 0x37, T09 => 0x36, T0a => 0x35, T0b => 0x34,
-	T0c => 0x33, T0d => 0x32, T0e => 0x31, T0f => 0x30,
-	T10 => 0x2f, T11 => 0x2e, T12 => 0x2d, T13 => 0x2c,
-	T14 => 0x2b, T15 => 0x2a, T16 => 0x29, T17 => 0x28,
-	T18 => 0x27, T19 => 0x26, T1a => 0x25, T1b => 0x24,
-	T1c => 0x23, T1d => 0x22, T1e => 0x21, T1f => 0x20,
-	T20 => 0x1f, T21 => 0x1e, T22 => 0x1d, T23 => 0x1c,
-	T24 => 0x1b, T25 => 0x1a, T26 => 0x19, T27 => 0x18,
-	T28 => 0x17, T29 => 0x16, T2a => 0x15, T2b => 0x14,
-	T2c => 0x13, T2d => 0x12, T2e => 0x11, T2f => 0x10,
-	T30 => 0x0f, T31 => 0x0e, T32 => 0x0d, T33 => 0x0c,
-	T34 => 0x0b, T35 => 0x0a, T36 => 0x09, T37 => 0x08,
-	T38 => 0x07, T39 => 0x06, T3a => 0x05, T3b => 0x04,
-	T3c => 0x03, T3d => 0x02, T3e => 0x01, T3f => 0x00,
-)}
-punch_card_impl_int! {u128, (T00,
-	T00 => 0x7f, T01 => 0x7e, T02 => 0x7d, T03 => 0x7c,
-	T04 => 0x7b, T05 => 0x7a, T06 => 0x79, T07 => 0x78,
-	T08 => 0x77, T09 => 0x76, T0a => 0x75, T0b => 0x74,
-	T0c => 0x73, T0d => 0x72, T0e => 0x71, T0f => 0x70,
-	T10 => 0x6f, T11 => 0x6e, T12 => 0x6d, T13 => 0x6c,
-	T14 => 0x6b, T15 => 0x6a, T16 => 0x69, T17 => 0x68,
-	T18 => 0x67, T19 => 0x66, T1a => 0x65, T1b => 0x64,
-	T1c => 0x63, T1d => 0x62, T1e => 0x61, T1f => 0x60,
-	T20 => 0x5f, T21 => 0x5e, T22 => 0x5d, T23 => 0x5c,
-	T24 => 0x5b, T25 => 0x5a, T26 => 0x59, T27 => 0x58,
-	T28 => 0x57, T29 => 0x56, T2a => 0x55, T2b => 0x54,
-	T2c => 0x53, T2d => 0x52, T2e => 0x51, T2f => 0x50,
-	T30 => 0x4f, T31 => 0x4e, T32 => 0x4d, T33 => 0x4c,
-	T34 => 0x4b, T35 => 0x4a, T36 => 0x49, T37 => 0x48,
-	T38 => 0x47, T39 => 0x46, T3a => 0x45, T3b => 0x44,
-	T3c => 0x43, T3d => 0x42, T3e => 0x41, T3f => 0x40,
-	T40 => 0x3f, T41 => 0x3e, T42 => 0x3d, T43 => 0x3c,
-	T44 => 0x3b, T45 => 0x3a, T46 => 0x39, T47 => 0x38,
-	T48 => 0x37, T49 => 0x36, T4a => 0x35, T4b => 0x34,
-	T4c => 0x33, T4d => 0x32, T4e => 0x31, T4f => 0x30,
-	T50 => 0x2f, T51 => 0x2e, T52 => 0x2d, T53 => 0x2c,
-	T54 => 0x2b, T55 => 0x2a, T56 => 0x29, T57 => 0x28,
-	T58 => 0x27, T59 => 0x26, T5a => 0x25, T5b => 0x24,
-	T5c => 0x23, T5d => 0x22, T5e => 0x21, T5f => 0x20,
-	T60 => 0x1f, T61 => 0x1e, T62 => 0x1d, T63 => 0x1c,
-	T64 => 0x1b, T65 => 0x1a, T66 => 0x19, T67 => 0x18,
-	T68 => 0x17, T69 => 0x16, T6a => 0x15, T6b => 0x14,
-	T6c => 0x13, T6d => 0x12, T6e => 0x11, T6f => 0x10,
-	T70 => 0x0f, T71 => 0x0e, T72 => 0x0d, T73 => 0x0c,
-	T74 => 0x0b, T75 => 0x0a, T76 => 0x09, T77 => 0x08,
-	T78 => 0x07, T79 => 0x06, T7a => 0x05, T7b => 0x04,
-	T7c => 0x03, T7d => 0x02, T7e => 0x01, T7f => 0x00,
-)}
 
+impl sealed::Line for RangeFull {}
+impl<T: PunchCardLine> sealed::Line for RangeTo<T> {}
+impl<T: PunchCardLine> sealed::Line for RangeToInclusive<T> {}
+// implemented for each kind of line instead of every `PunchCardLine`, so errors point at the row of a tuple that doesn't match
+impl LinesMatch for RangeFull {}
+impl<T: PunchCardLine> LinesMatch for RangeTo<T> {}
+impl<T: PunchCardLine> LinesMatch for RangeToInclusive<T> {}
 impl PunchCardLine for RangeFull {
 	const HEAD: Option<bool> = None;
 	type Tail = Self;
 	const LENGTH: usize = 0;
+	const BITS: u128 = 0;
+	type Length = Len<0>;
 }
 impl<T: PunchCardLine> PunchCardLine for RangeTo<T> {
 	const HEAD: Option<bool> = Some(false);
 	type Tail = T;
 	const LENGTH: usize = Self::Tail::LENGTH + 1;
+	const BITS: u128 = Self::Tail::BITS;
+	type Length = <T::Length as ArrayLength>::Next;
 }
 impl<T: PunchCardLine> PunchCardLine for RangeToInclusive<T> {
 	const HEAD: Option<bool> = Some(true);
 	type Tail = T;
 	const LENGTH: usize = Self::Tail::LENGTH + 1;
+	const BITS: u128 = Self::Tail::BITS | 1 << Self::Tail::LENGTH;
+	type Length = <T::Length as ArrayLength>::Next;
 }
@@ -32,31 +32,100 @@
 //!
 //! Run [`.punch_card()`](PunchCard::punch_card) on a card to convert it into an array of values
 //!
-//! By default, punch-card supports the following sizes of card:
+//! Cards can be anywhere from 1 to 128 lines tall, each column is read as the smallest type that fits it:
 //!
 //! - *n* &times; 1 &rarr; array of [`bool`]
-//! - *n* &times; 8 &rarr; array of [`u8`] (probably the one you'll be using the most)
-//! - *n* &times; 16 &rarr; array of [`u16`]
-//! - *n* &times; 32 &rarr; array of [`u32`]
-//! - *n* &times; 64 &rarr; array of [`u64`]
-//! - *n* &times; 128 &rarr; array of [`u128`]
+//! - *n* &times; 2&ndash;8 &rarr; array of [`u8`] (probably the one you'll be using the most)
+//! - *n* &times; 9&ndash;16 &rarr; array of [`u16`]
+//! - *n* &times; 17&ndash;32 &rarr; array of [`u32`]
+//! - *n* &times; 33&ndash;64 &rarr; array of [`u64`]
+//! - *n* &times; 65&ndash;128 &rarr; array of [`u128`]
+//!
+//! Taller cards are made by [stacking](tall) several cards, and read as arrays of limbs.
+//!
+//! Columns can also be read as any other type implementing [`FromColumn`] using [`.punch_card_as::<T>()`](PunchCard::punch_card_as), such as signed integers ([`i8`] through [`i128`]), larger unsigned integers, floats ([`f32`] and [`f64`] from 32 and 64 lines), or `[bool; height]`.
+//!
+//! The top line is the most significant bit, [`.punch_card_lsb()`](PunchCard::punch_card_lsb) and [`LsbFirst`] read it as the least significant bit instead, without having to draw the card upside down.
+//!
+//! [`.columns()`](PunchCard::columns) iterates over the columns lazily instead, for cards too large to keep on the stack, and [`.punch_card_into(out)`](PunchCard::punch_card_into) writes them into an existing slice.
+//!
+//! 8-line cards can be read as wider integers using [`.punch_card_words::<T, N>(endian)`](PunchCard::punch_card_words), where every few columns become one word, in the same order on every target.
+//!
+//! Single-line cards can be packed into bytes using [`.punch_card_packed::<N>()`](PunchCard::punch_card_packed), for long bitmaps that would take eight times the space as an array of [`bool`].
+//!
+//! Structs and enums are read from byte cards using [`.punch_card_decode::<T>(endian)`](PunchCard::punch_card_decode), with `#[derive(FromPunchCard)]` from the `macros` feature.
+//!
+//! Cards can also be read sideways using [`.punch_card_rows::<T>()`](PunchCard::punch_card_rows), where each line of up to 128 columns becomes one integer.
+//!
+//! Some types only accept some columns, like [`char`] for 12-line [Hollerith](hollerith) cards, those are read using [`.try_punch_card_as::<T>()`](PunchCard::try_punch_card_as).
+//! Your own types can be read the same way by implementing [`FromPunchBits`](internal::FromPunchBits).
+//!
+//! 9-line cards with a parity line, like 9-track tape, are checked and read as bytes using the [`parity`] types.
+//!
+//! [`punch_card_const`] decodes a card at compile time, for `const` and `static` lookup tables.
+//!
+//! Large cards can be kept in their own files and loaded with `include_card!` from the `macros` feature.
+//!
+//! `punch!`, also from the `macros` feature, writes cards with one glyph per hole instead, like `# . # #`, which is easier to read for tall cards.
+//!
+//! Values are turned back into card source using [`render::write_card`].
+//!
+//! Cards can be inspected and edited at runtime as a [`Card`](card::Card), made from any card with [`From`] or parsed from card source with [`str::parse`].
 //!
 //! A card is simply a tuple of some amount of rows, where each row is a chain of `..`'s or `..=`'s terminated by a `..`, as shown in the above example.
 //!
-//! *Note: this uses the [`generic_const_exprs`](https://github.com/rust-lang/rust/issues/76560) feature, it should be safe to use though.*
-#![allow(incomplete_features)]
-#![feature(generic_const_exprs)]
-#![feature(test)]
-// needed for a test
-#![recursion_limit = "512"]
+//! Works on stable Rust, where lines can be up to 1024 columns long.
+//! The `nightly` feature lifts that limit by adding `_array` versions of the methods, like `punch_card_array`, which return `[T; Self::LENGTH]` using the [`generic_const_exprs`](https://github.com/rust-lang/rust/issues/76560) feature, it should be safe to use though.
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+#![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
+#![cfg_attr(all(feature = "nightly", any(test, doctest)), feature(test))]
+// needed for tests, the lines longer than 1024 columns of the `nightly` tests need more
+#![cfg_attr(not(feature = "nightly"), recursion_limit = "512")]
+#![cfg_attr(feature = "nightly", recursion_limit = "2048")]
 #![deny(missing_docs)]
 #![no_std]
 
-use internal::PunchCardInner;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use internal::{
+	Array, ArrayLength, FromBytes, FromColumn, FromRow, LinesMatch, LsbFirst, PunchCardInner,
+	TryFromColumn,
+};
+
+/// The array with one entry per column of a card, the same type with or without the `nightly` feature
+macro_rules! columns {
+	($t:ty) => { <<Self as PunchCard>::Length as ArrayLength>::Array<$t> };
+}
+/// The array with one entry per column of a card, used by the `nightly` methods for lines longer than 1024 columns
+#[cfg(feature = "nightly")]
+macro_rules! array {
+	($t:ty) => {
+		[$t; <Self as PunchCard>::LENGTH]
+	};
+}
+/// The array with one entry per line of a card
+macro_rules! rows {
+	($t:ty) => { <<Self as PunchCard>::Height as ArrayLength>::Array<$t> };
+}
 
+pub mod card;
+pub mod hollerith;
 pub mod internal;
-#[cfg(test)]
+pub mod parity;
+pub mod render;
+pub mod tall;
+
+pub use internal::FromPunchCard;
+#[cfg(feature = "macros")]
+pub use punch_card_macros::{include_card, punch, FromPunchCard};
+#[cfg(any(test, doctest))]
 pub mod tests;
+// lets `#[derive(FromPunchCard)]` find this crate in tests
+#[cfg(test)]
+extern crate self as punch_card;
 
 /// A punch card, formatted like this:
 /// ```rust
@@ -78,24 +147,418 @@ pub mod tests;
 /// ```
 /// An `=` indicates a one bit and a space indicates a zero bit.
 ///
-/// implemented for punched cards of heights 1, 8, 16, 32, 64, and 128.
+/// implemented for punched cards of heights 1 through 128.
 ///
-/// If you want to make your own punch card type, implement [`PunchCardInner`] instead, as there's a blanked implementation of `PunchCard` for all `PunchCardInner`.
+/// All lines of a card have to be the same length, a card with mismatched lines fails `cargo check` with an error naming the first line that doesn't match, see [`LinesMatch`].
+/// Lines longer than 1024 columns are only compared when the card is built, so with the `nightly` feature those only fail `cargo build`.
 pub trait PunchCard {
 	#[doc(hidden)]
 	const LENGTH: usize;
+	#[doc(hidden)]
+	const HEIGHT: usize;
+	/// The amount of columns as a type, the outputs are `<Self::Length as ArrayLength>::Array<T>`, which is `[T; length]`
+	type Length: ArrayLength;
+	/// The amount of lines as a type, like [`Length`](PunchCard::Length)
+	type Height: ArrayLength;
+	/// Bits of each column of the tape, see [`Bits`](internal::Bits)
+	type Column: Copy + Default + FromColumn<Self::Column>;
 	/// Type for each column of the tape
 	type Output;
 	/// Parses the punch card into your output format of choice.
-	fn punch_card(&self) -> [Self::Output; Self::LENGTH];
+	fn punch_card(&self) -> columns!(Self::Output)
+	where
+		Self: LinesMatch;
+	/// Parses the punch card like [`punch_card`](PunchCard::punch_card), but returns an error for mismatched lines instead of failing to compile.
+	fn try_punch_card(&self) -> Result<columns!(Self::Output), PunchCardError>;
+	/// Parses the punch card into the start of `out`, returning how many columns were written, or an error if `out` is too short.
+	///
+	/// Pass `&mut out[offset..]` to write at an offset.
+	fn punch_card_into(&self, out: &mut [Self::Output]) -> Result<usize, PunchCardError>
+	where
+		Self: LinesMatch;
+	/// Parses the punch card, converting each column into another type, like `punch_card_as::<i8>()` for signed values on an 8-line card.
+	fn punch_card_as<T: FromColumn<Self::Column>>(&self) -> columns!(T)
+	where
+		Self: LinesMatch;
+	/// Parses the punch card, converting each column into a type that not every column is valid for, like `try_punch_card_as::<char>()` for [Hollerith](hollerith) cards.
+	fn try_punch_card_as<T: TryFromColumn<Self::Column>>(
+		&self,
+	) -> Result<columns!(T), PunchCardError>;
+	/// Parses the punch card like [`punch_card`](PunchCard::punch_card), but with the top line as the least significant bit, for devices that expect LSB-first data.
+	fn punch_card_lsb(&self) -> columns!(Self::Output)
+	where
+		LsbFirst<Self::Output>: FromColumn<Self::Column>,
+		Self: LinesMatch;
+	/// Parses the punch card like [`punch_card`](PunchCard::punch_card), but into `[T; Self::LENGTH]`, so lines can be longer than 1024 columns.
+	#[cfg(feature = "nightly")]
+	fn punch_card_array(&self) -> array!(Self::Output)
+	where
+		Self: LinesMatch;
+	/// Parses the punch card like [`try_punch_card`](PunchCard::try_punch_card), but into `[T; Self::LENGTH]`.
+	#[cfg(feature = "nightly")]
+	fn try_punch_card_array(&self) -> Result<array!(Self::Output), PunchCardError>;
+	/// Parses the punch card like [`punch_card_as`](PunchCard::punch_card_as), but into `[T; Self::LENGTH]`.
+	#[cfg(feature = "nightly")]
+	fn punch_card_as_array<T: FromColumn<Self::Column>>(&self) -> array!(T)
+	where
+		Self: LinesMatch;
+	/// Parses the punch card like [`try_punch_card_as`](PunchCard::try_punch_card_as), but into `[T; Self::LENGTH]`.
+	#[cfg(feature = "nightly")]
+	fn try_punch_card_as_array<T: TryFromColumn<Self::Column>>(
+		&self,
+	) -> Result<array!(T), PunchCardError>;
+	/// Parses the punch card like [`punch_card_lsb`](PunchCard::punch_card_lsb), but into `[T; Self::LENGTH]`.
+	#[cfg(feature = "nightly")]
+	fn punch_card_lsb_array(&self) -> array!(Self::Output)
+	where
+		LsbFirst<Self::Output>: FromColumn<Self::Column>,
+		Self: LinesMatch;
+	/// Iterates over the columns of the punch card one at a time, without building the whole array like [`punch_card`](PunchCard::punch_card) does.
+	fn columns(&self) -> Columns<Self>
+	where
+		Self: PunchCardInner + LinesMatch;
+	/// Parses the punch card sideways, reading each line into one integer with the leftmost column as the most significant bit.
+	fn punch_card_rows<T: FromRow>(&self) -> rows!(T)
+	where
+		Self: LinesMatch;
+	/// Parses a card of up to 8 lines into words, each made from [`size_of::<T>()`](core::mem::size_of) consecutive byte columns in the given byte order.
+	///
+	/// The amount of words is taken from the output type, a card that doesn't split into exactly that many words fails to compile.
+	/// Like [`punch_card_decode`](PunchCard::punch_card_decode), the card can be at most 1024 columns long.
+	fn punch_card_words<T: FromBytes, const N: usize>(&self, endian: Endian) -> [T; N]
+	where
+		u8: FromColumn<Self::Column>,
+		Self: LinesMatch;
+	/// Parses a single-line card into bytes, eight columns each, with the first column as the most significant bit of the first byte.
+	///
+	/// The last byte is padded with zeroes, the amount of bytes is taken from the output type and has to be the length of the card divided by 8, rounded up.
+	/// Works for lines of any length, the bits are set one column at a time without decoding into [`bool`]s first.
+	fn punch_card_packed<const N: usize>(&self) -> [u8; N]
+	where
+		bool: FromColumn<Self::Column>,
+		Self: LinesMatch;
+	/// Parses a single-line card into bytes like [`punch_card_packed`](PunchCard::punch_card_packed), but with the first column as the least significant bit of the first byte.
+	fn punch_card_packed_lsb<const N: usize>(&self) -> [u8; N]
+	where
+		bool: FromColumn<Self::Column>,
+		Self: LinesMatch;
+	/// Parses a card of up to 8 lines into a [`FromPunchCard`] type, like a struct with `#[derive(FromPunchCard)]`, with `endian` as the byte order of fields that don't pick their own.
+	///
+	/// Returns an error at the first column of a value that isn't valid, like a `bool` that isn't 0 or 1 or an unknown enum discriminant.
+	/// A card that isn't exactly as long as the type fails to compile.
+	///
+	/// The card is decoded into a buffer of bytes first, so it can be at most 1024 columns long, even with the `nightly` feature.
+	fn punch_card_decode<T: FromPunchCard>(&self, endian: Endian) -> Result<T, PunchCardError>
+	where
+		u8: FromColumn<Self::Column>,
+		Self: LinesMatch;
 }
 
 impl<T: PunchCardInner> PunchCard for T {
 	const LENGTH: usize = T::LENGTH;
+	const HEIGHT: usize = T::HEIGHT;
+	type Length = T::Length;
+	type Height = T::Height;
+	type Column = T::Column;
 	type Output = T::Output;
-	fn punch_card(&self) -> [Self::Output; <Self as PunchCard>::LENGTH] {
-		let mut out = [Default::default(); <Self as PunchCard>::LENGTH];
-		Self::eval_part(&mut out, 0);
+	fn punch_card(&self) -> columns!(Self::Output)
+	where
+		Self: LinesMatch,
+	{
+		let () = Self::LENGTHS_MATCH;
+		let mut out: columns!(Self::Output) = Array::from_fn(|_| Default::default());
+		Self::eval_part(out.as_mut_slice(), 0);
 		out
 	}
+	fn try_punch_card(&self) -> Result<columns!(Self::Output), PunchCardError> {
+		let mut out: columns!(Self::Output) = Array::from_fn(|_| Default::default());
+		Self::try_eval_part(out.as_mut_slice(), 0)?;
+		Ok(out)
+	}
+	fn punch_card_into(&self, out: &mut [Self::Output]) -> Result<usize, PunchCardError>
+	where
+		Self: LinesMatch,
+	{
+		let () = Self::LENGTHS_MATCH;
+		let length = <Self as PunchCard>::LENGTH;
+		let capacity = out.len();
+		let out = out
+			.get_mut(..length)
+			.ok_or(PunchCardError::OutputTooShort { length, capacity })?;
+		Self::eval_part(out, 0);
+		Ok(length)
+	}
+	fn punch_card_as<U: FromColumn<Self::Column>>(&self) -> columns!(U)
+	where
+		Self: LinesMatch,
+	{
+		let () = Self::LENGTHS_MATCH;
+		let mut columns: columns!(Self::Column) = Array::from_fn(|_| Default::default());
+		Self::eval_part(columns.as_mut_slice(), 0);
+		let columns = columns.as_slice();
+		Array::from_fn(|i| U::from_column(columns[i]))
+	}
+	fn try_punch_card_as<U: TryFromColumn<Self::Column>>(
+		&self,
+	) -> Result<columns!(U), PunchCardError> {
+		let mut columns: columns!(Self::Column) = Array::from_fn(|_| Default::default());
+		Self::try_eval_part(columns.as_mut_slice(), 0)?;
+		let columns = columns.as_slice();
+		let mut out: columns!(Option<U>) = Array::from_fn(|i| U::try_from_column(columns[i]));
+		if let Some(column) = out.as_slice().iter().position(Option::is_none) {
+			return Err(PunchCardError::InvalidColumn { column });
+		}
+		let out = out.as_mut_slice();
+		Ok(Array::from_fn(|i| out[i].take().expect("checked above")))
+	}
+	fn punch_card_lsb(&self) -> columns!(Self::Output)
+	where
+		LsbFirst<Self::Output>: FromColumn<Self::Column>,
+		Self: LinesMatch,
+	{
+		let out: columns!(LsbFirst<Self::Output>) = self.punch_card_as();
+		let out = out.as_slice();
+		Array::from_fn(|i| out[i].0)
+	}
+	#[cfg(feature = "nightly")]
+	fn punch_card_array(&self) -> array!(Self::Output)
+	where
+		Self: LinesMatch,
+	{
+		self.punch_card_as_array()
+	}
+	#[cfg(feature = "nightly")]
+	fn try_punch_card_array(&self) -> Result<array!(Self::Output), PunchCardError> {
+		let mut out = core::array::from_fn(|_| Default::default());
+		Self::try_eval_part(&mut out, 0)?;
+		Ok(out)
+	}
+	#[cfg(feature = "nightly")]
+	fn punch_card_as_array<U: FromColumn<Self::Column>>(&self) -> array!(U)
+	where
+		Self: LinesMatch,
+	{
+		let () = Self::LENGTHS_MATCH;
+		let mut columns: array!(Self::Column) = core::array::from_fn(|_| Default::default());
+		Self::eval_part(&mut columns, 0);
+		columns.map(U::from_column)
+	}
+	#[cfg(feature = "nightly")]
+	fn try_punch_card_as_array<U: TryFromColumn<Self::Column>>(
+		&self,
+	) -> Result<array!(U), PunchCardError> {
+		let mut columns: array!(Self::Column) = core::array::from_fn(|_| Default::default());
+		Self::try_eval_part(&mut columns, 0)?;
+		let mut out = columns.map(U::try_from_column);
+		if let Some(column) = out.iter().position(Option::is_none) {
+			return Err(PunchCardError::InvalidColumn { column });
+		}
+		Ok(core::array::from_fn(|i| {
+			out[i].take().expect("checked above")
+		}))
+	}
+	#[cfg(feature = "nightly")]
+	fn punch_card_lsb_array(&self) -> array!(Self::Output)
+	where
+		LsbFirst<Self::Output>: FromColumn<Self::Column>,
+		Self: LinesMatch,
+	{
+		self.punch_card_as_array::<LsbFirst<Self::Output>>()
+			.map(|LsbFirst(out)| out)
+	}
+	fn columns(&self) -> Columns<Self>
+	where
+		Self: PunchCardInner + LinesMatch,
+	{
+		let () = Self::LENGTHS_MATCH;
+		Columns {
+			range: 0..<Self as PunchCard>::LENGTH,
+			card: PhantomData,
+		}
+	}
+	fn punch_card_rows<U: FromRow>(&self) -> rows!(U)
+	where
+		Self: LinesMatch,
+	{
+		let mut out: rows!(U) = Array::from_fn(|_| U::from_row(0));
+		Self::eval_rows(out.as_mut_slice());
+		out
+	}
+	fn punch_card_words<U: FromBytes, const N: usize>(&self, endian: Endian) -> [U; N]
+	where
+		u8: FromColumn<Self::Column>,
+		Self: LinesMatch,
+	{
+		let () = Self::LENGTHS_MATCH;
+		let () = internal::WordsFit::<Self, U, N>::OK;
+		let () = internal::BytesFit::<Self>::OK;
+		let mut bytes = [0; internal::CONST_LENGTH];
+		Self::eval_part(&mut bytes[..<Self as PunchCard>::LENGTH], 0);
+		core::array::from_fn(|i| U::from_bytes(&bytes[i * U::BYTES..][..U::BYTES], endian))
+	}
+	fn punch_card_packed<const N: usize>(&self) -> [u8; N]
+	where
+		bool: FromColumn<Self::Column>,
+		Self: LinesMatch,
+	{
+		internal::packed::<Self, N>(false)
+	}
+	fn punch_card_packed_lsb<const N: usize>(&self) -> [u8; N]
+	where
+		bool: FromColumn<Self::Column>,
+		Self: LinesMatch,
+	{
+		internal::packed::<Self, N>(true)
+	}
+	fn punch_card_decode<U: FromPunchCard>(&self, endian: Endian) -> Result<U, PunchCardError>
+	where
+		u8: FromColumn<Self::Column>,
+		Self: LinesMatch,
+	{
+		let () = Self::LENGTHS_MATCH;
+		let () = internal::DecodeFits::<Self, U>::OK;
+		let () = internal::BytesFit::<Self>::OK;
+		let mut bytes = [0; internal::CONST_LENGTH];
+		let bytes = &mut bytes[..<Self as PunchCard>::LENGTH];
+		Self::eval_part(bytes, 0);
+		U::from_punch_bytes(bytes, endian)
+			.map_err(|column| PunchCardError::InvalidColumn { column })
+	}
+}
+
+/// Parses a punch card at compile time, for initializing `const` and `static` items:
+///
+/// ```rust
+/// use punch_card::punch_card_const;
+///
+/// #[rustfmt::skip]
+/// static ARROW: [u8; 5] = punch_card_const((
+///     .. .. ..=.. .. ..,
+///     .. ..=..=..=.. ..,
+///     ..=.. ..=.. ..=..,
+///     .. .. ..=.. .. ..,
+/// ));
+/// assert_eq!(ARROW, [2, 4, 15, 4, 2]);
+/// ```
+///
+/// Works for any card with lines of up to 1024 items, returning the same array as [`punch_card`](PunchCard::punch_card).
+/// The length is taken from the type of the item, a length that doesn't match the card fails to compile.
+pub const fn punch_card_const<C: PunchCardInner + LinesMatch, const N: usize>(
+	card: C,
+) -> [C::Output; N] {
+	core::mem::forget(card);
+	internal::decoded::<C, N>()
+}
+
+/// The order of the bytes in a word made from several columns, see [`punch_card_words`](PunchCard::punch_card_words).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+	/// The first column is the least significant byte
+	Little,
+	/// The first column is the most significant byte
+	Big,
 }
+
+/// An iterator over the columns of a punch card, returned by [`columns`](PunchCard::columns).
+///
+/// Each column is read from the card's type when it's reached, so nothing is stored besides the range of remaining columns.
+pub struct Columns<C: PunchCardInner + ?Sized> {
+	range: Range<usize>,
+	card: PhantomData<fn() -> C>,
+}
+impl<C: PunchCardInner + ?Sized> Clone for Columns<C> {
+	fn clone(&self) -> Self {
+		Self {
+			range: self.range.clone(),
+			card: PhantomData,
+		}
+	}
+}
+impl<C: PunchCardInner + ?Sized> fmt::Debug for Columns<C> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Columns")
+			.field("range", &self.range)
+			.finish()
+	}
+}
+impl<C: PunchCardInner + ?Sized> Iterator for Columns<C> {
+	type Item = C::Output;
+	#[inline]
+	fn next(&mut self) -> Option<C::Output> {
+		C::eval_column(self.range.next()?)
+	}
+	#[inline]
+	fn nth(&mut self, n: usize) -> Option<C::Output> {
+		C::eval_column(self.range.nth(n)?)
+	}
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.range.size_hint()
+	}
+}
+impl<C: PunchCardInner + ?Sized> DoubleEndedIterator for Columns<C> {
+	#[inline]
+	fn next_back(&mut self) -> Option<C::Output> {
+		C::eval_column(self.range.next_back()?)
+	}
+	#[inline]
+	fn nth_back(&mut self, n: usize) -> Option<C::Output> {
+		C::eval_column(self.range.nth_back(n)?)
+	}
+}
+impl<C: PunchCardInner + ?Sized> ExactSizeIterator for Columns<C> {}
+impl<C: PunchCardInner + ?Sized> FusedIterator for Columns<C> {}
+
+/// An error from parsing a malformed punch card.
+///
+/// Line lengths are compared against the first line of the card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PunchCardError {
+	/// A line ended before the first line did
+	RowTooShort {
+		/// Index of the offending line
+		row: usize,
+		/// Column where the line ended
+		column: usize,
+	},
+	/// A line kept going after the first line ended
+	RowTooLong {
+		/// Index of the offending line
+		row: usize,
+		/// Column where the first line ended
+		column: usize,
+	},
+	/// A column isn't a valid value for the output type
+	InvalidColumn {
+		/// Index of the offending column
+		column: usize,
+	},
+	/// The output slice has room for fewer columns than the card has
+	OutputTooShort {
+		/// Amount of columns in the card
+		length: usize,
+		/// Length of the output slice
+		capacity: usize,
+	},
+}
+
+impl fmt::Display for PunchCardError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::RowTooShort { row, column } => {
+				write!(f, "row {row} is too short, it ends at column {column}")
+			}
+			Self::RowTooLong { row, column } => write!(
+				f,
+				"row {row} is too long, it continues past column {column}"
+			),
+			Self::InvalidColumn { column } => write!(f, "column {column} isn't a valid value"),
+			Self::OutputTooShort { length, capacity } => write!(
+				f,
+				"the card has {length} columns, but the output only has room for {capacity}"
+			),
+		}
+	}
+}
+
+impl core::error::Error for PunchCardError {}
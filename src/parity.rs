@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT
+//! Cards with a parity line, like 9-track tape, where each column is 8 data bits and one parity bit.
+//!
+//! Columns of a 9-line card are checked and decoded into a [`u8`] using [`EvenParity`] or [`OddParity`], with the position of the parity line as the parameter, counting from the top:
+//!
+//! ```rust
+//! use punch_card::parity::EvenParity;
+//! use punch_card::{PunchCard, PunchCardError};
+//!
+//! #[rustfmt::skip]
+//! let card = (
+//!     ..=.. ..=..=.. ..,
+//!     .. .. .. .. .. ..,
+//!     ..=.. .. .. .. ..,
+//!     .. .. .. .. .. ..,
+//!     .. .. .. .. .. ..,
+//!     .. .. .. .. .. ..,
+//!     .. .. .. .. .. ..,
+//!     .. .. .. .. .. ..,
+//!     .. .. ..=.. .. ..,
+//! );
+//! assert_eq!(
+//!     card.try_punch_card_as::<EvenParity<0>>(),
+//!     Err(PunchCardError::InvalidColumn { column: 3 }),
+//! );
+//! ```
+//!
+//! The data bits keep their order with the parity line taken out, so the top remaining line is the most significant bit.
+
+use crate::internal::{Bits, TryFromColumn};
+
+/// Fails to evaluate if `ROW` isn't one of the 9 lines
+struct ParityRow<const ROW: usize>;
+impl<const ROW: usize> ParityRow<ROW> {
+	const OK: () = assert!(ROW < 9, "the parity line has to be one of the 9 lines");
+}
+
+/// Removes line `row` from a 9-line column, returns [`None`] if the amount of punched holes doesn't have parity `odd`
+const fn check(bits: Bits<9>, row: usize, odd: bool) -> Option<u8> {
+	let bits = bits.to_u128() as u16;
+	if (bits.count_ones() % 2 == 1) != odd {
+		return None;
+	}
+	// line `row` is bit `8 - row`, the lines below it shift up by one
+	let below = 8 - row;
+	let low = bits & ((1 << below) - 1);
+	let high = bits >> (below + 1) << below;
+	Some((high | low) as u8)
+}
+
+macro_rules! parity {
+	($($(#[$meta:meta])* $name:ident => $odd:literal,)*) => {$(
+		$(#[$meta])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+		pub struct $name<const ROW: usize>(pub u8);
+		impl<const ROW: usize> TryFromColumn<Bits<9>> for $name<ROW> {
+			#[inline]
+			fn try_from_column(bits: Bits<9>) -> Option<Self> {
+				let () = ParityRow::<ROW>::OK;
+				match check(bits, ROW, $odd) {
+					Some(data) => Some(Self(data)),
+					None => None,
+				}
+			}
+		}
+		impl<const ROW: usize> From<$name<ROW>> for u8 {
+			#[inline]
+			fn from(value: $name<ROW>) -> Self {
+				value.0
+			}
+		}
+	)*};
+}
+parity! {
+	/// A byte from a 9-line column where line `ROW` makes the amount of punched holes even.
+	EvenParity => false,
+	/// A byte from a 9-line column where line `ROW` makes the amount of punched holes odd.
+	OddParity => true,
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: MIT
+//! Rendering values back into card source.
+//!
+//! [`write_card`] writes the tuple source that decodes back into the same values, one line per bit:
+//!
+//! ```rust
+//! use punch_card::render::write_card;
+//!
+//! let mut source = String::new();
+//! write_card(&mut source, &[5u8, 0, 255]).unwrap();
+//! assert_eq!(source, "(
+//! \t.. .. ..=..,
+//! \t.. .. ..=..,
+//! \t.. .. ..=..,
+//! \t.. .. ..=..,
+//! \t.. .. ..=..,
+//! \t..=.. ..=..,
+//! \t.. .. ..=..,
+//! \t..=.. ..=..,
+//! )");
+//! ```
+//!
+//! Cards of other heights are rendered from [`Bits`], and LSB-first cards from [`LsbFirst`].
+
+use core::fmt;
+
+use crate::internal::{Bits, LsbFirst};
+
+/// Conversion from a value into the bits of one column, the inverse of [`FromColumn`](crate::internal::FromColumn).
+///
+/// Implemented for [`bool`], integers, floats, [`Bits`], and [`LsbFirst`] of any of these.
+pub trait ToColumn: Copy {
+	/// The amount of lines in the column
+	const HEIGHT: usize;
+	/// The bits of the column, with the top line as the most significant of the lowest [`HEIGHT`](ToColumn::HEIGHT) bits.
+	fn to_column(self) -> u128;
+}
+
+impl ToColumn for bool {
+	const HEIGHT: usize = 1;
+	#[inline(always)]
+	fn to_column(self) -> u128 {
+		self as u128
+	}
+}
+impl<const H: usize> ToColumn for Bits<H> {
+	const HEIGHT: usize = H;
+	#[inline(always)]
+	fn to_column(self) -> u128 {
+		self.to_u128()
+	}
+}
+impl<T: ToColumn> ToColumn for LsbFirst<T> {
+	const HEIGHT: usize = T::HEIGHT;
+	#[inline(always)]
+	fn to_column(self) -> u128 {
+		if T::HEIGHT == 0 {
+			0
+		} else {
+			self.0.to_column().reverse_bits() >> (128 - T::HEIGHT)
+		}
+	}
+}
+macro_rules! to_column_int {
+	($($unsigned:ty, $signed:ty;)*) => {$(
+		impl ToColumn for $unsigned {
+			const HEIGHT: usize = <$unsigned>::BITS as usize;
+			#[inline(always)]
+			fn to_column(self) -> u128 {
+				self as u128
+			}
+		}
+		impl ToColumn for $signed {
+			const HEIGHT: usize = <$signed>::BITS as usize;
+			#[inline(always)]
+			fn to_column(self) -> u128 {
+				self as $unsigned as u128
+			}
+		}
+	)*};
+}
+to_column_int! {
+	u8, i8;
+	u16, i16;
+	u32, i32;
+	u64, i64;
+	u128, i128;
+}
+macro_rules! to_column_float {
+	($($float:ty;)*) => {$(
+		impl ToColumn for $float {
+			const HEIGHT: usize = core::mem::size_of::<$float>() * 8;
+			#[inline(always)]
+			fn to_column(self) -> u128 {
+				self.to_bits() as u128
+			}
+		}
+	)*};
+}
+to_column_float! {
+	f32;
+	f64;
+}
+
+/// Writes the source of a card holding `columns`, with every line ending in `..,`.
+///
+/// Decoding the written card with [`punch_card`](crate::PunchCard::punch_card) gives back the same values.
+pub fn write_card<T: ToColumn>(out: &mut impl fmt::Write, columns: &[T]) -> fmt::Result {
+	out.write_str("(\n")?;
+	for row in (0..T::HEIGHT).rev() {
+		out.write_char('\t')?;
+		for column in columns {
+			out.write_str(if column.to_column() >> row & 1 == 1 {
+				"..="
+			} else {
+				".. "
+			})?;
+		}
+		out.write_str("..,\n")?;
+	}
+	out.write_char(')')
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+//! Cards taller than 128 lines, like 256-bit hashes or 512-bit curve points.
+//!
+//! A tall card is written as a tuple of ordinary cards stacked on top of each other, each up to 128 lines tall and all the same length.
+//! Each column is read into an array of limbs using [`.punch_card_limbs()`](TallPunchCard::punch_card_limbs), with the first limb holding the top lines:
+//!
+//! ```rust
+//! use punch_card::tall::TallPunchCard;
+//!
+//! #[rustfmt::skip]
+//! let columns: [[u8; 2]; 3] = (
+//!     (
+//!         .. ..=.. ..,
+//!         .. .. .. ..,
+//!         ..=.. .. ..,
+//!         .. .. .. ..,
+//!     ),
+//!     (
+//!         .. .. .. ..,
+//!         .. .. .. ..,
+//!         ..=.. .. ..,
+//!         .. .. .. ..,
+//!         .. .. .. ..,
+//!         .. .. .. ..,
+//!         .. .. .. ..,
+//!         .. .. ..=..,
+//!         .. .. ..=..,
+//!     ),
+//! ).punch_card_limbs();
+//! assert_eq!(columns, [[0b100, 0b0100_0000], [0b1_0000, 0], [0, 0b11]]);
+//! ```
+//!
+//! Like with integers, the column is the number with the top line as the most significant bit.
+//! Cards shorter than the limbs are padded with zeroes at the top.
+
+use core::marker::PhantomData;
+
+use crate::internal::{FromColumn, LinesMatch, PunchCardInner};
+
+/// Integers that a tall column is split into, see [`punch_card_limbs`](TallPunchCard::punch_card_limbs).
+///
+/// Implemented for the unsigned integers.
+pub trait Limb: Copy + Default {
+	/// The amount of lines in one limb
+	const BITS: usize;
+	/// Sets bit `bit`, counting from the least significant bit.
+	fn set_bit(&mut self, bit: usize);
+}
+macro_rules! limb_int {
+	($($type:ty),*) => {$(
+		impl Limb for $type {
+			const BITS: usize = <$type>::BITS as usize;
+			#[inline(always)]
+			fn set_bit(&mut self, bit: usize) {
+				*self |= 1 << bit;
+			}
+		}
+	)*};
+}
+limb_int! {u8, u16, u32, u64, u128}
+
+/// A card made from several cards stacked on top of each other, for cards taller than 128 lines.
+///
+/// Implemented for tuples of up to 8 cards, for up to 1024 lines.
+pub trait TallPunchCard {
+	/// The amount of columns, the same for all stacked cards
+	#[doc(hidden)]
+	const LENGTH: usize;
+	/// The amount of lines of all stacked cards together
+	const HEIGHT: usize;
+	/// Parses each column into `K` limbs of type `T`, the first limb being the most significant.
+	///
+	/// The amount of columns and limbs are taken from the output type, stacked cards of different lengths, a card with a different amount of columns, or columns that don't fit into the limbs fail to compile.
+	fn punch_card_limbs<T: Limb, const K: usize, const N: usize>(&self) -> [[T; K]; N];
+}
+
+/// Fails to evaluate if card `C` doesn't fit into `N` columns of `K` limbs of `T`
+struct LimbsFit<C: ?Sized, T, const K: usize, const N: usize>(PhantomData<(fn() -> C, T)>);
+impl<C: TallPunchCard + ?Sized, T: Limb, const K: usize, const N: usize> LimbsFit<C, T, K, N> {
+	const OK: () = assert!(
+		C::LENGTH == N && C::HEIGHT <= K * T::BITS,
+		"the card doesn't fit into the output columns"
+	);
+}
+
+/// Sets the bits of `column`, a column of `height` lines, into `limbs` with its bottom line at bit `bottom`
+#[inline(always)]
+fn set_bits<T: Limb, const K: usize>(
+	limbs: &mut [T; K],
+	column: u128,
+	height: usize,
+	bottom: usize,
+) {
+	for line in 0..height {
+		if column >> line & 1 == 1 {
+			let bit = bottom + line;
+			limbs[K - 1 - bit / T::BITS].set_bit(bit % T::BITS);
+		}
+	}
+}
+
+macro_rules! tall_impl {
+	($first:ident $(, $rest:ident)*) => {
+		impl<$first: PunchCardInner + LinesMatch, $($rest: PunchCardInner + LinesMatch),*> TallPunchCard for ($first, $($rest,)*)
+		where
+			u128: FromColumn<$first::Column> $(+ FromColumn<$rest::Column>)*,
+		{
+			const LENGTH: usize = {
+				assert!(true $(&& $rest::LENGTH == $first::LENGTH)*, "mismatched card lengths");
+				$first::LENGTH
+			};
+			const HEIGHT: usize = $first::HEIGHT $(+ $rest::HEIGHT)*;
+			fn punch_card_limbs<T: Limb, const K: usize, const N: usize>(&self) -> [[T; K]; N] {
+				let () = $first::LENGTHS_MATCH;
+				$(let () = $rest::LENGTHS_MATCH;)*
+				let () = LimbsFit::<Self, T, K, N>::OK;
+				core::array::from_fn(|i| {
+					let mut limbs = [T::default(); K];
+					// the cards are read from the top, each one ending further down
+					let mut bottom = Self::HEIGHT;
+					bottom -= $first::HEIGHT;
+					set_bits(&mut limbs, $first::eval_column(i).unwrap_or_default(), $first::HEIGHT, bottom);
+					$(
+						bottom -= $rest::HEIGHT;
+						set_bits(&mut limbs, $rest::eval_column(i).unwrap_or_default(), $rest::HEIGHT, bottom);
+					)*
+					limbs
+				})
+			}
+		}
+	};
+}
+tall_impl!(C0);
+tall_impl!(C0, C1);
+tall_impl!(C0, C1, C2);
+tall_impl!(C0, C1, C2, C3);
+tall_impl!(C0, C1, C2, C3, C4);
+tall_impl!(C0, C1, C2, C3, C4, C5);
+tall_impl!(C0, C1, C2, C3, C4, C5, C6);
+tall_impl!(C0, C1, C2, C3, C4, C5, C6, C7);
@@ -1,11 +1,19 @@
 // SPDX-License-Identifier: MIT
 //! Tests and the like
 
+#[cfg(feature = "nightly")]
 extern crate test;
 
+#[cfg(feature = "nightly")]
 use test::{black_box, Bencher};
 
-use crate::PunchCard;
+use crate::card::{Card, Expected, ParseCardError};
+use crate::hollerith::{decode_029, Ebcdic, Ibm026Commercial, Ibm026Fortran};
+use crate::internal::{Array, ArrayLength, Bits, FromColumn, FromPunchBits, LinesMatch, LsbFirst};
+use crate::parity::{EvenParity, OddParity};
+use crate::render::write_card;
+use crate::tall::TallPunchCard;
+use crate::{punch_card_const, Endian, PunchCard, PunchCardError};
 
 /// using a punch card to store strings
 #[test]
@@ -33,16 +41,9 @@ fn string_decode() {
 /// using a punch card to store a struct
 #[test]
 fn struct_decode() {
-	#[derive(Debug, PartialEq, Eq)]
-	#[repr(C)]
-	struct Output {
-		a: [u32; 3],
-		b: u16,
-		c: u16,
-	}
+	// `a: [u32; 3]`, then `b: u16` and `c: u16` sharing the last word
 	#[rustfmt::skip]
-	#[cfg(target_endian = "little")]
-	let data = (
+	let card = (
 		.. .. ..=.. .. ..=.. .. .. .. .. .. .. .. ..=.. ..,
 		.. .. .. .. .. ..=.. .. .. .. .. .. ..=.. .. .. ..,
 		..=.. ..=.. .. ..=.. .. .. .. .. .. ..=..=.. .. ..,
@@ -51,26 +52,118 @@ fn struct_decode() {
 		.. .. ..=.. ..=..=..=.. ..=..=.. .. ..=.. .. ..=..,
 		..=..=..=.. ..=..=..=.. .. ..=.. .. .. ..=..=..=..,
 		..=.. ..=.. ..=..=..=.. ..=..=.. .. .. .. ..=..=..,
-	).punch_card();
-	#[rustfmt::skip]
-	#[cfg(target_endian = "big")]
-	let data = (
-		.. ..=.. .. .. .. ..=.. .. .. .. .. .. ..=.. .. ..,
-		.. .. .. .. .. .. ..=.. .. .. .. .. .. .. .. ..=..,
-		.. ..=.. ..=.. .. ..=.. .. .. .. .. .. .. ..=..=..,
-		.. .. .. ..=.. .. .. .. .. .. .. ..=.. .. ..=.. ..,
-		.. .. ..=.. .. .. .. .. .. .. .. .. .. .. .. ..=..,
-		.. ..=.. .. .. ..=..=..=.. .. ..=..=..=.. .. ..=..,
-		.. ..=..=..=.. ..=..=..=.. .. ..=.. ..=..=..=.. ..,
-		.. ..=.. ..=.. ..=..=..=.. .. ..=..=..=..=.. .. ..,
-	).punch_card();
-	// safety: these are all integers so any bit combination is valid
-	let output: Output = unsafe {core::mem::transmute(data)};
-	assert_eq!(output, Output {
-		a: [10947123, 517895, 1813],
-		b: 12908,
-		c: 1923,
-	});
+	);
+	assert_eq!(
+		card.punch_card_words::<u32, 4>(Endian::Little),
+		[10947123, 517895, 1813, 12908 | 1923 << 16]
+	);
+	// on every target, big-endian words read the same bytes the other way around
+	assert_eq!(
+		card.punch_card_words::<u32, 4>(Endian::Big),
+		[0x330AA700, 0x07E70700, 0x15070000, 0x6C328307]
+	);
+}
+
+/// structs and enums can be derived, with their own byte orders
+#[cfg(feature = "macros")]
+#[test]
+fn derive_decode() {
+	use crate::FromPunchCard;
+
+	#[derive(Debug, PartialEq, FromPunchCard)]
+	#[repr(u16)]
+	enum Kind {
+		Text = 1,
+		Image = 0x200,
+	}
+	#[derive(Debug, PartialEq, FromPunchCard)]
+	struct Flags(bool, bool);
+	#[derive(Debug, PartialEq, FromPunchCard)]
+	struct Header {
+		kind: Kind,
+		#[punch(bytes = 3)]
+		length: u32,
+		#[punch(little)]
+		checksum: u16,
+		flags: Flags,
+	}
+	#[rustfmt::skip]
+	let card = (
+		.. .. .. .. .. ..=.. .. .. ..,
+		.. .. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. .. ..,
+		..=.. .. .. ..=.. .. .. .. ..,
+		.. .. .. ..=..=.. ..=.. ..=..,
+	);
+	assert_eq!(
+		card.punch_card_decode::<Header>(Endian::Big),
+		Ok(Header {
+			kind: Kind::Image,
+			length: 0x000103,
+			checksum: 0x0180,
+			flags: Flags(false, true),
+		})
+	);
+	assert_eq!(
+		card.punch_card_decode::<Header>(Endian::Little),
+		Err(PunchCardError::InvalidColumn { column: 0 })
+	);
+	#[rustfmt::skip]
+	let card = (
+		.. .. ..,
+		.. .. ..,
+		.. .. ..,
+		.. .. ..,
+		.. .. ..,
+		.. .. ..,
+		.. ..=..,
+		..=.. ..,
+	);
+	assert_eq!(
+		card.punch_card_decode::<[Flags; 1]>(Endian::Big),
+		Err(PunchCardError::InvalidColumn { column: 1 })
+	);
+	// narrower signed fields keep their sign
+	#[derive(Debug, PartialEq, FromPunchCard)]
+	struct Delta(#[punch(bytes = 2)] i32, #[punch(bytes = 2, little)] i64);
+	#[rustfmt::skip]
+	let card = (
+		..=..=.. .. ..,
+		..=..=.. .. ..,
+		..=..=.. .. ..,
+		..=..=.. .. ..,
+		..=..=.. .. ..,
+		..=..=..=.. ..,
+		..=..=.. .. ..,
+		..=.. ..=.. ..,
+	);
+	assert_eq!(card.punch_card_decode(Endian::Big), Ok(Delta(-2, 5)));
+	assert_eq!(card.punch_card_decode(Endian::Little), Ok(Delta(-257, 5)));
+	// wider discriminants are told apart by all their bytes
+	#[derive(Debug, PartialEq, FromPunchCard)]
+	#[repr(u16)]
+	enum Mode {
+		Big = 256,
+		Zero = 0,
+	}
+	#[rustfmt::skip]
+	let card = (
+		.. .. .. .. ..,
+		.. .. .. .. ..,
+		.. .. .. .. ..,
+		.. .. .. .. ..,
+		.. .. .. .. ..,
+		.. .. .. .. ..,
+		.. .. .. .. ..,
+		..=.. .. .. ..,
+	);
+	assert_eq!(
+		card.punch_card_decode::<[Mode; 2]>(Endian::Big),
+		Ok([Mode::Big, Mode::Zero])
+	);
 }
 
 /// bool parsing can't ever length mismatch lol
@@ -112,6 +205,583 @@ fn valid_u8_0() {
 	]);
 }
 
+/// any height works, columns are read as the smallest type that fits
+#[test]
+fn valid_u5() {
+	#[rustfmt::skip]
+	let card = (
+		..=.. ..=.. ..,
+		.. ..=..=.. ..,
+		..=.. ..=.. ..,
+		.. ..=..=.. ..,
+		..=.. ..=.. ..,
+	);
+	assert_eq!(card.punch_card(), [21u8, 10, 31, 0]);
+	assert_eq!(card.punch_card_as::<i8>(), [-11, 10, -1, 0]);
+	assert_eq!(card.punch_card_as::<u32>(), [21, 10, 31, 0]);
+	assert_eq!(
+		card.punch_card_as::<[bool; 5]>()[0],
+		[true, false, true, false, true]
+	);
+}
+
+/// columns can be read with the top line as the least significant bit
+#[test]
+fn valid_u8_lsb() {
+	#[rustfmt::skip]
+	let card = (
+		.. .. ..=..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. ..=.. ..,
+		..=.. .. ..,
+	);
+	assert_eq!(card.punch_card(), [1, 2, 128]);
+	assert_eq!(card.punch_card_lsb(), [128, 64, 1]);
+	assert_eq!(
+		card.punch_card_as::<LsbFirst<i8>>(),
+		[LsbFirst(-128), LsbFirst(64), LsbFirst(1)]
+	);
+	#[rustfmt::skip]
+	assert_eq!((..=.. ..,).punch_card_lsb(), [true, false]);
+}
+
+/// a fixed-size [`core::fmt::Write`] target, since tests don't have `String`
+struct Buffer<const N: usize>([u8; N], usize);
+impl<const N: usize> Buffer<N> {
+	fn new() -> Self {
+		Self([0; N], 0)
+	}
+	fn as_str(&self) -> &str {
+		core::str::from_utf8(&self.0[..self.1]).unwrap()
+	}
+}
+impl<const N: usize> core::fmt::Write for Buffer<N> {
+	fn write_str(&mut self, s: &str) -> core::fmt::Result {
+		let end = self.1 + s.len();
+		self.0
+			.get_mut(self.1..end)
+			.ok_or(core::fmt::Error)?
+			.copy_from_slice(s.as_bytes());
+		self.1 = end;
+		Ok(())
+	}
+}
+
+/// rendered cards decode back into the values they were rendered from
+#[test]
+fn render_round_trip() {
+	// a full column, an empty one and one with only the top line
+	#[rustfmt::skip]
+	let card = (
+		..=.. .. ..=..,
+		..=.. .. .. ..,
+		..=.. .. .. ..,
+	);
+	let source = "(
+\t..=.. .. ..=..,
+\t..=.. .. .. ..,
+\t..=.. .. .. ..,
+)";
+	let mut out = Buffer::<128>::new();
+	write_card(&mut out, &card.punch_card_as::<Bits<3>>()).unwrap();
+	assert_eq!(out.as_str(), source);
+
+	let mut out = Buffer::<128>::new();
+	write_card(&mut out, &[] as &[Bits<3>]).unwrap();
+	assert_eq!(out.as_str(), "(\n\t..,\n\t..,\n\t..,\n)");
+
+	let mut out = Buffer::<128>::new();
+	write_card(&mut out, &[LsbFirst(true), LsbFirst(false)]).unwrap();
+	assert_eq!(out.as_str(), "(\n\t..=.. ..,\n)");
+
+	let mut out = Buffer::<512>::new();
+	let values = [0x1234u16, 0xfedc];
+	write_card(&mut out, &values).unwrap();
+	#[rustfmt::skip]
+	assert_eq!((
+		.. ..=..,
+		.. ..=..,
+		.. ..=..,
+		..=..=..,
+		.. ..=..,
+		.. ..=..,
+		..=..=..,
+		.. .. ..,
+		.. ..=..,
+		.. ..=..,
+		..=.. ..,
+		..=..=..,
+		.. ..=..,
+		..=..=..,
+		.. .. ..,
+		.. .. ..,
+	).punch_card(), values);
+	let card: Card<2, 16> = out.as_str().parse().unwrap();
+	assert_eq!(card.columns_as::<u16>(), values);
+	let mut short = Buffer::<16>::new();
+	assert!(write_card(&mut short, &values).is_err());
+}
+
+/// cards can be read into part of an existing buffer
+#[test]
+fn into_slice() {
+	#[rustfmt::skip]
+	let card = (
+		..=.. .. ..,
+		.. ..=.. ..,
+	);
+	let mut buffer = [0xAAu8; 5];
+	// exactly filling the rest of the buffer
+	assert_eq!(card.punch_card_into(&mut buffer[2..]), Ok(3));
+	assert_eq!(buffer, [0xAA, 0xAA, 2, 1, 0]);
+	// one column short, leaving the buffer untouched
+	let mut buffer = [0xAAu8; 2];
+	assert_eq!(
+		card.punch_card_into(&mut buffer),
+		Err(PunchCardError::OutputTooShort {
+			length: 3,
+			capacity: 2
+		})
+	);
+	assert_eq!(buffer, [0xAA, 0xAA]);
+	assert_eq!((.., ..).punch_card_into(&mut []), Ok(0));
+}
+
+/// single-line cards pack eight columns into each byte
+#[test]
+fn packed_bits() {
+	#[rustfmt::skip]
+	let card = (..=.. ..=..=.. .. .. ..=.. ..=..=..,);
+	assert_eq!(card.punch_card_packed(), [0xB1, 0x60]);
+	assert_eq!(card.punch_card_packed_lsb(), [0x8D, 0x06]);
+	#[rustfmt::skip]
+	assert_eq!(
+		(..=.. ..=..=.. .. .. ..=.. ..=..=..).punch_card_packed(),
+		[0xB1, 0x60]
+	);
+}
+
+/// cards can be edited at runtime and written back out
+#[test]
+fn runtime_card() {
+	#[rustfmt::skip]
+	let mut card: Card<4, 3> = Card::from((
+		..=.. ..=.. ..,
+		.. ..=.. .. ..,
+		..=..=.. .. ..,
+	));
+	assert_eq!(card.columns_as::<u8>(), [0b101, 0b011, 0b100, 0]);
+	assert!(card[(1, 1)] && !card[(3, 2)]);
+	card.set(3, 2);
+	card.clear(0, 0);
+	card.punch(2, 0, false);
+	assert_eq!(card.columns_as::<u8>(), [0b001, 0b011, 0, 0b001]);
+	let mut out = Buffer::<64>::new();
+	core::fmt::Write::write_fmt(&mut out, format_args!("{card}")).unwrap();
+	assert_eq!(
+		out.as_str(),
+		"(\n\t.. .. .. .. ..,\n\t.. ..=.. .. ..,\n\t..=..=.. ..=..,\n)"
+	);
+	#[rustfmt::skip]
+	assert_eq!(
+		Card::<2, 1>::from(..=.. ..),
+		Card::from_columns([Bits::new(1), Bits::new(0)])
+	);
+	assert_eq!(Card::<4, 3>::default(), Card::new());
+}
+
+/// card source is parsed at runtime, with the position of any mistakes
+#[test]
+fn parse_card() {
+	let card: Card<4, 3> = "(
+		..=.. ..=.. .., // a comment
+		.. ..=.. .. ..,
+		..=..=.. .. ..,
+	)"
+	.parse()
+	.unwrap();
+	assert_eq!(card.columns_as::<u8>(), [0b101, 0b011, 0b100, 0]);
+	let mut out = Buffer::<64>::new();
+	core::fmt::Write::write_fmt(&mut out, format_args!("{card}")).unwrap();
+	assert_eq!(out.as_str().parse(), Ok(card));
+	#[cfg(feature = "macros")]
+	assert_eq!(
+		include_str!("tests/massive.card")
+			.parse::<Card<256, 128>>()
+			.map(|card| card.columns_as::<u128>()),
+		Ok(massive_card())
+	);
+	fn parse_error<const W: usize, const H: usize>(text: &str) -> ParseCardError {
+		text.parse::<Card<W, H>>().unwrap_err()
+	}
+	let error = |line, column, expected| ParseCardError {
+		line,
+		column,
+		expected,
+	};
+	assert_eq!(
+		parse_error::<2, 2>("..=.. ..,\n.. ..,"),
+		error(2, 6, Expected::Columns(2))
+	);
+	assert_eq!(
+		parse_error::<2, 1>("..=.. .. ..,"),
+		error(1, 7, Expected::Columns(2))
+	);
+	assert_eq!(parse_error::<1, 1>("(..=..)"), error(1, 7, Expected::Comma));
+	assert_eq!(
+		parse_error::<1, 1>("(..=..,"),
+		error(1, 8, Expected::CloseParen)
+	);
+	assert_eq!(parse_error::<1, 1>("(..=..,))"), error(1, 9, Expected::End));
+	assert_eq!(
+		parse_error::<1, 2>("..=..,"),
+		error(1, 7, Expected::Lines(2))
+	);
+	assert_eq!(
+		parse_error::<1, 1>("..=..,\n.. ..,"),
+		error(2, 1, Expected::Lines(1))
+	);
+	assert_eq!(parse_error::<1, 1>("..=..x"), error(1, 6, Expected::Token));
+	assert_eq!(
+		parse_error::<2, 1>("..=..=,"),
+		error(1, 7, Expected::LineEnd)
+	);
+	assert_eq!(parse_error::<1, 1>(". ..,"), error(1, 1, Expected::Hole));
+	assert_eq!(parse_error::<2, 1>("......,"), error(1, 3, Expected::Hole));
+	assert_eq!(parse_error::<2, 1>("..=....,"), error(1, 6, Expected::Hole));
+	let mut out = Buffer::<64>::new();
+	let message = error(2, 6, Expected::Columns(2));
+	core::fmt::Write::write_fmt(&mut out, format_args!("{message}")).unwrap();
+	assert_eq!(
+		out.as_str(),
+		"expected a line of 2 columns at line 2, column 6"
+	);
+}
+
+/// bitmaps longer than 1024 columns can be packed too
+#[cfg(all(feature = "nightly", feature = "macros"))]
+#[test]
+fn packed_primes() {
+	let mut primes = [0u8; 138];
+	for n in 2..1100 {
+		if (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0) {
+			primes[n / 8] |= 0x80 >> (n % 8);
+		}
+	}
+	assert_eq!(
+		crate::include_card!("tests/primes.card").punch_card_packed(),
+		primes
+	);
+}
+
+/// lines longer than 1024 columns are read into `[T; LENGTH]` by the `_array` methods
+#[cfg(all(feature = "nightly", feature = "macros"))]
+#[test]
+fn array_primes() {
+	let card = crate::include_card!("tests/primes.card");
+	let primes: [bool; 1100] = card.punch_card_array();
+	assert!(primes[2] && primes[1097] && !primes[1099]);
+	assert_eq!(card.try_punch_card_array(), Ok(primes));
+	assert_eq!(card.punch_card_as_array::<u8>()[1097], 1);
+	assert_eq!(card.punch_card_lsb_array(), primes);
+}
+
+/// cards can initialize constants and statics
+#[test]
+fn const_card() {
+	#[rustfmt::skip]
+	static COUNT: [u8; 4] = punch_card_const((
+		.. .. ..=..=..,
+		.. ..=.. ..=..,
+	));
+	#[rustfmt::skip]
+	const FLAGS: [bool; 5] = punch_card_const(..=.. ..=..=.. ..);
+	const EMPTY: [u16; 0] = punch_card_const((.., .., .., .., .., .., .., .., .., ..));
+	assert_eq!(COUNT, [0, 1, 2, 3]);
+	assert_eq!(FLAGS, [true, false, true, true, false]);
+	assert_eq!(EMPTY, []);
+	#[cfg(feature = "macros")]
+	{
+		static MASSIVE: [u128; 256] = punch_card_const(crate::include_card!("tests/massive.card"));
+		assert_eq!(MASSIVE, massive_card());
+	}
+}
+
+/// columns can be iterated lazily, from both ends
+#[test]
+fn columns_iter() {
+	#[rustfmt::skip]
+	let card = (
+		..=.. ..=.. ..,
+		.. ..=..=.. ..,
+		.. .. ..=..=..,
+	);
+	let mut columns = card.columns();
+	assert_eq!(columns.len(), 4);
+	assert_eq!(columns.next(), Some(4));
+	assert_eq!(columns.next_back(), Some(1));
+	assert_eq!(columns.len(), 2);
+	assert!(columns.eq([2, 7]));
+	assert!(card.columns().rev().eq([1, 7, 2, 4]));
+	assert_eq!(card.columns().nth(2), Some(7));
+	assert_eq!(card.columns().nth_back(3), Some(4));
+	assert_eq!(card.columns().nth(4), None);
+	// both ends meet in the middle without repeating a column
+	let mut columns = card.columns();
+	assert_eq!(columns.nth(1), Some(2));
+	assert_eq!(columns.nth_back(0), Some(1));
+	assert_eq!(columns.next_back(), Some(7));
+	assert_eq!(columns.next(), None);
+	assert_eq!(columns.next_back(), None);
+	#[rustfmt::skip]
+	assert!((..=.. ..,).columns().eq([true, false]));
+	assert_eq!((.., ..).columns().next(), None);
+}
+
+/// rows can be read sideways, one integer per line
+#[test]
+fn rows_glyph() {
+	#[rustfmt::skip]
+	let card = (
+		.. ..=..=..=.. .. .. .. ..,
+		..=.. .. .. ..=.. .. .. ..,
+		..=..=..=..=..=.. .. .. ..,
+		..=.. .. .. ..=.. .. .. ..,
+		..=.. .. .. ..=.. .. .. ..,
+	);
+	assert_eq!(card.punch_card_rows::<u8>(), [0x70, 0x88, 0xF8, 0x88, 0x88]);
+	assert_eq!(
+		card.punch_card_rows::<u16>(),
+		[0x70, 0x88, 0xF8, 0x88, 0x88]
+	);
+	#[rustfmt::skip]
+	assert_eq!((..=.. ..=..,).punch_card_rows::<u8>(), [0b101]);
+}
+
+/// 12-line cards are read as Hollerith cards
+#[test]
+fn hollerith_029() {
+	#[rustfmt::skip]
+	let card = (
+		.. .. .. ..=..=.. ..=..=.. ..=.. .. ..=.. .. .. ..=..=.. .. .. .. .. ..,
+		..=.. ..=.. .. ..=.. .. ..=.. .. .. .. .. .. .. .. .. ..=.. ..=.. .. ..,
+		.. ..=.. .. .. .. .. .. .. .. .. .. .. .. ..=.. .. .. .. .. .. .. ..=..,
+		.. .. .. .. .. .. .. ..=.. .. .. ..=.. .. .. .. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. .. .. .. .. .. ..=.. .. .. .. .. .. .. .. .. ..,
+		.. .. .. ..=.. .. ..=.. .. .. .. .. ..=.. ..=.. .. ..=.. .. ..=.. .. ..,
+		.. ..=.. .. .. .. .. .. .. ..=.. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+		.. .. ..=.. .. .. .. .. .. .. .. .. .. .. .. .. ..=.. ..=.. .. ..=.. ..,
+		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+		..=.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..=..,
+		.. .. .. .. ..=.. .. .. .. .. .. .. ..=.. ..=.. ..=.. ..=.. ..=.. ..=..,
+		.. .. .. .. .. .. .. .. ..=.. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+	);
+	let text = card.try_punch_card_as::<char>().unwrap();
+	assert!(text.iter().copied().eq("PUNCH-CARD 1.2, (C) $5?".chars()));
+	#[rustfmt::skip]
+	assert_eq!((
+		..=..=..=..,
+		.. ..=.. ..,
+		.. .. .. ..,
+		..=..=.. ..,
+		.. .. ..=..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+	).try_punch_card_as::<char>(), Err(PunchCardError::InvalidColumn { column: 1 }));
+}
+
+/// other code pages are picked with the output type
+#[test]
+fn hollerith_code_pages() {
+	#[rustfmt::skip]
+	let card = (
+		.. .. .. ..=..=..=..=.. .. ..=..,
+		.. .. .. .. .. .. .. ..=.. .. ..,
+		..=.. ..=.. .. .. .. .. .. .. ..,
+		.. .. .. ..=.. .. .. .. .. .. ..,
+		.. .. .. .. .. ..=.. .. ..=.. ..,
+		.. ..=.. .. .. .. .. .. .. ..=..,
+		.. .. ..=.. .. .. ..=..=.. .. ..,
+		.. .. .. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. .. .. ..,
+		..=.. .. .. .. .. .. .. .. .. ..,
+		.. ..=..=.. .. .. ..=..=.. ..=..,
+		.. .. .. .. .. .. .. .. .. .. ..,
+	);
+	let text = card.try_punch_card_as::<Ibm026Fortran>().unwrap();
+	assert!(text
+		.map(char::from)
+		.iter()
+		.copied()
+		.eq("X=(A+B)*2.".chars()));
+	// the same punches mean different things in the commercial set
+	let text = card.try_punch_card_as::<Ibm026Commercial>().unwrap();
+	assert!(text
+		.map(char::from)
+		.iter()
+		.copied()
+		.eq("X#%A&B⌑*2.".chars()));
+	#[rustfmt::skip]
+	let card = (
+		..=.. ..=..=..=..,
+		..=..=..=.. .. ..,
+		.. ..=.. ..=..=..,
+		.. .. .. .. .. ..,
+		.. .. .. .. .. ..,
+		.. .. .. ..=.. ..,
+		.. ..=.. .. .. ..,
+		.. .. ..=.. .. ..,
+		.. .. .. .. .. ..,
+		..=.. .. .. .. ..,
+		.. .. .. .. ..=..,
+		.. .. .. .. .. ..,
+	);
+	let bytes = card.try_punch_card_as::<Ebcdic>().unwrap();
+	assert_eq!(bytes.map(u8::from), [0x97, 0xa4, 0x95, 0x83, 0x88]);
+	assert!(bytes.map(char::from).iter().copied().eq("punch".chars()));
+	assert_eq!(
+		card.try_punch_card_as::<char>(),
+		Err(PunchCardError::InvalidColumn { column: 0 })
+	);
+}
+
+/// every EBCDIC byte has its own punches, and they agree with the 029 code
+#[test]
+fn hollerith_ebcdic() {
+	for byte in 0..=255 {
+		assert_eq!(Ebcdic::decode(Ebcdic(byte).to_bits()), Some(Ebcdic(byte)));
+	}
+	for bits in 0..1 << 12 {
+		let bits = Bits::<12>::new(bits);
+		if let Some(c) = decode_029(bits) {
+			assert_eq!(Ebcdic::decode(bits).map(char::from), Some(c));
+		}
+	}
+}
+
+/// 9-line cards check their parity line
+#[test]
+fn parity_9() {
+	#[rustfmt::skip]
+	let card = (
+		.. .. .. ..,
+		..=.. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. ..=.. ..,
+		.. ..=.. ..,
+		..=..=.. ..,
+		.. ..=.. ..,
+	);
+	assert_eq!(
+		card.try_punch_card_as::<EvenParity<8>>(),
+		Ok([EvenParity(0x41), EvenParity(0x07), EvenParity(0x00)])
+	);
+	assert_eq!(
+		card.try_punch_card_as::<EvenParity<4>>(),
+		Ok([EvenParity(0x42), EvenParity(0x0F), EvenParity(0x00)])
+	);
+	assert_eq!(
+		card.try_punch_card_as::<OddParity<8>>(),
+		Err(PunchCardError::InvalidColumn { column: 0 })
+	);
+}
+
+/// a decimal digit, for reading columns into a user type
+#[derive(Debug, PartialEq)]
+struct Digit(u8);
+impl FromPunchBits for Digit {
+	fn from_punch_bits(height: usize, bits: u128) -> Option<Self> {
+		(height <= 8 && bits < 10).then_some(Self(bits as u8))
+	}
+}
+
+/// user types can be read from columns of any height
+#[test]
+fn from_punch_bits() {
+	#[rustfmt::skip]
+	assert_eq!((
+		.. ..=..=..,
+		.. .. .. ..,
+		.. .. .. ..,
+		..=..=.. ..,
+	).try_punch_card_as::<Digit>(), Ok([Digit(1), Digit(9), Digit(8)]));
+	#[rustfmt::skip]
+	assert_eq!((
+		.. ..=..=..,
+		.. .. ..=..,
+		.. .. .. ..,
+		..=..=.. ..,
+	).try_punch_card_as::<Digit>(), Err(PunchCardError::InvalidColumn { column: 2 }));
+	#[rustfmt::skip]
+	assert_eq!(
+		(..=..=.., ..=.. ..,).try_punch_card_as::<Digit>(),
+		Ok([Digit(3), Digit(2)])
+	);
+}
+
+/// 32 and 64-line cards read as floats, keeping NaN payloads
+#[test]
+fn valid_f32() {
+	#[rustfmt::skip]
+	let card = (
+		.. ..=.. ..,
+		.. ..=..=..,
+		..=.. ..=..,
+		..=.. ..=..,
+		..=.. ..=..,
+		..=.. ..=..,
+		..=.. ..=..,
+		..=.. ..=..,
+		..=.. ..=..,
+		.. .. .. ..,
+		.. ..=..=..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. .. ..,
+		.. .. ..=..,
+	);
+	let [one, minus, nan] = card.punch_card_as::<f32>();
+	assert_eq!((one, minus), (1.0, -2.5));
+	assert!(nan.is_nan());
+	assert_eq!(nan.to_bits(), 0x7FA00001);
+	let mut source = Buffer::<512>::new();
+	write_card(&mut source, &[nan]).unwrap();
+	assert!(source.as_str().starts_with("(\n\t.. ..,\n\t..=..,\n"));
+	let nan = f64::from_bits(0xFFF0_0000_DEAD_BEEF);
+	assert_eq!(
+		f64::from_column(Bits::new(nan.to_bits() as u128)).to_bits(),
+		nan.to_bits()
+	);
+}
+
 /// zero-length cards are valid
 #[test]
 fn valid_u8_1() {
@@ -122,38 +792,195 @@ fn valid_u8_1() {
 	);
 }
 
-/// line 0 was used in older versions as the source of truth, that's a bad idea
+/// generic code can name the length types and the outputs built from them, with or without the `nightly` feature
 #[test]
-#[should_panic]
-fn invalid_u8_0() {
+fn length_types() {
+	fn zeroes<C: PunchCard>(_: &C) -> <C::Length as ArrayLength>::Array<u8> {
+		Array::from_fn(|_| 0)
+	}
+	fn lines<C: PunchCard>(_: &C) -> <C::Height as ArrayLength>::Array<usize> {
+		Array::from_fn(|row| row)
+	}
+	fn decode<C: PunchCard + LinesMatch>(card: &C) -> <C::Length as ArrayLength>::Array<C::Output> {
+		card.punch_card()
+	}
 	#[rustfmt::skip]
-	(
-		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
-		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
-		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
-		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=..=..=..=..=..=..=..=..=..,
-		.. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=.. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=..,
-		.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=..,
-		.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=..,
-		.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=..,
-	).punch_card();
+	let card = (
+		..=.. .. ..,
+		.. .. ..=..,
+	);
+	assert_eq!(zeroes(&card), [0, 0, 0]);
+	assert_eq!(lines(&card), [0, 1]);
+	assert_eq!(decode(&card), [2, 0, 1]);
 }
 
+/// line 0 was used in older versions as the source of truth, that's a bad idea
+/// ```compile_fail
+/// use punch_card::PunchCard;
+///
+/// (
+///     .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+///     .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+///     .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+///     .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=..=..=..=..=..=..=..=..=..,
+///     .. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=.. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=..,
+///     .. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=..,
+///     .. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=..,
+///     .. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=..,
+/// ).punch_card();
+/// ```
+#[cfg(doctest)]
+pub struct InvalidU8_0;
+
 /// in said older versions the other lines would be padded with zeros
+/// ```compile_fail
+/// use punch_card::PunchCard;
+///
+/// (
+///     .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+///     .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+///     .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+///     .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=..=..=..=..=..=..=..=..=..,
+///     .. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=.. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=..,
+///     .. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=..,
+///     .. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=..,
+///     .. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=..,
+/// ).punch_card();
+/// ```
+#[cfg(doctest)]
+pub struct InvalidU8_1;
+
+/// rows that are too wide for the output type don't compile
+/// ```compile_fail
+/// use punch_card::PunchCard;
+/// (.. .. .. .. .. .. .. .. ..=..,).punch_card_rows::<u8>();
+/// ```
+#[cfg(doctest)]
+pub struct InvalidRows;
+
+/// compile-time cards have to match the length of their item
+/// ```compile_fail
+/// use punch_card::punch_card_const;
+/// static CARD: [u8; 2] = punch_card_const((..=.. ..=.., .. .. ..=..,));
+/// ```
+#[cfg(doctest)]
+pub struct InvalidConstLength;
+
+/// the parity line has to be on the card
+/// ```compile_fail
+/// use punch_card::{parity::EvenParity, PunchCard};
+/// (.. ..,.. ..,.. ..,.. ..,.. ..,.. ..,.. ..,.. ..,.. ..,).try_punch_card_as::<EvenParity<9>>();
+/// ```
+#[cfg(doctest)]
+pub struct InvalidParityRow;
+
+/// cards have to split evenly into words
+/// ```compile_fail
+/// use punch_card::{Endian, PunchCard};
+/// let words: [u16; 1] = (..=.. .. ..=..,).punch_card_words(Endian::Big);
+/// ```
+#[cfg(doctest)]
+pub struct InvalidWords;
+
+/// cards have to be as long as the type they're decoded into
+/// ```compile_fail
+/// use punch_card::{Endian, PunchCard};
+/// (..=.. ..,).punch_card_decode::<u32>(Endian::Big);
+/// ```
+#[cfg(doctest)]
+pub struct InvalidDecodeLength;
+
+/// packed cards have to fill the output bytes
+/// ```compile_fail
+/// use punch_card::PunchCard;
+/// let bytes: [u8; 2] = (..=.. ..=..,).punch_card_packed();
+/// ```
+#[cfg(doctest)]
+pub struct InvalidPacked;
+
+/// enums with fields can't be derived
+/// ```compile_fail
+/// #[derive(punch_card::FromPunchCard)]
+/// enum Value {
+///     Byte(u8),
+/// }
+/// ```
+#[cfg(all(doctest, feature = "macros"))]
+pub struct InvalidDerive;
+
+/// enum discriminants have to fit in their `#[repr]`, `u8` by default
+/// ```compile_fail
+/// #[derive(punch_card::FromPunchCard)]
+/// enum Mode {
+///     Big = 256,
+///     Zero = 0,
+/// }
+/// ```
+#[cfg(all(doctest, feature = "macros"))]
+pub struct InvalidDiscriminant;
+
+/// stacked cards have to be the same length
+/// ```compile_fail
+/// use punch_card::tall::TallPunchCard;
+/// let limbs: [[u8; 1]; 2] = ((..=.. ..,), (.. .. .. ..,)).punch_card_limbs();
+/// ```
+#[cfg(doctest)]
+pub struct InvalidTall;
+
+/// runtime cards have to be the same size as the card they're made from
+/// ```compile_fail
+/// use punch_card::card::Card;
+/// let card: Card<3, 1> = Card::from(..=.. ..);
+/// ```
+#[cfg(doctest)]
+pub struct InvalidCardSize;
+
+/// glyph lines have to be the same length
+/// ```compile_fail
+/// punch_card::punch! {
+///     # . #
+///     # .
+/// };
+/// ```
+#[cfg(all(doctest, feature = "macros"))]
+pub struct InvalidGlyphs;
+
+/// mismatched cards can still be parsed fallibly
 #[test]
-#[should_panic]
-fn invalid_u8_1() {
+fn try_invalid_u8() {
 	#[rustfmt::skip]
-	(
-		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+	assert_eq!((
 		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
 		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..,
 		.. .. .. .. .. .. .. .. .. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=..=..=..=..=..=..=..=..=..,
 		.. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=.. .. .. .. .. .. .. .. ..=..=..=..=..=..=..=..=..,
 		.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=.. .. .. .. ..=..=..=..=..,
 		.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=.. .. ..=..=..,
 		.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=.. ..=..,
-	).punch_card();
+	).try_punch_card(), Err(PunchCardError::RowTooLong { row: 1, column: 31 }));
+	#[rustfmt::skip]
+	assert_eq!((
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. ..=..=..=..=..,
+		.. .. ..=..=..,
+		.. ..=.. ..=.. ..=.. ..=..,
+	).try_punch_card(), Err(PunchCardError::RowTooShort { row: 6, column: 4 }));
+	#[rustfmt::skip]
+	assert_eq!((
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. .. .. .. .. ..,
+		.. .. .. .. ..=..=..=..=..,
+		.. .. ..=..=.. .. ..=..=..,
+		.. ..=.. ..=.. ..=.. ..=..,
+	).try_punch_card(), Ok([0, 1, 2, 3, 4, 5, 6, 7]));
 }
 
 /// these also work to test slice inputting
@@ -184,6 +1011,34 @@ fn valid_u16() {
 	);
 }
 
+/// signed values are read as two's complement
+#[test]
+fn valid_i16() {
+	#[rustfmt::skip]
+	assert_eq!(
+		(
+			.. ..=.. ..=..,
+			.. ..=.. ..=..,
+			.. ..=..=.. ..,
+			.. ..=..=.. ..,
+			..=.. ..=.. ..,
+			..=.. ..=.. ..,
+			..=.. .. ..=..,
+			..=.. .. ..=..,
+			.. ..=.. ..=..,
+			.. ..=.. ..=..,
+			.. ..=..=.. ..,
+			.. ..=..=.. ..,
+			..=.. ..=.. ..,
+			..=.. ..=.. ..,
+			..=.. .. ..=..,
+			..=.. .. ..=..,
+		)
+			.punch_card_as::<i16>(),
+		[0x0F0F, -0x0F10, 0x3C3C, -0x3C3D],
+	);
+}
+
 #[test]
 fn valid_u32() {
 	#[rustfmt::skip]
@@ -459,6 +1314,107 @@ fn massive_u128() {
 	]);
 }
 
+/// cards can be loaded from files
+#[cfg(feature = "macros")]
+#[test]
+fn include_card() {
+	assert_eq!(
+		crate::include_card!("tests/massive.card").punch_card(),
+		massive_card()
+	);
+	assert!(crate::include_card!("tests/massive.card")
+		.columns()
+		.rev()
+		.eq(massive_card().into_iter().rev()));
+	assert_eq!(
+		crate::include_card!("tests/glyph.card", '#', '.').punch_card_rows::<u8>(),
+		[0x70, 0x88, 0xF8, 0x88, 0x88]
+	);
+	// a space glyph is read verbatim, trailing space included
+	assert_eq!(
+		crate::include_card!("tests/spaces.card", '#', ' ').punch_card(),
+		[3u8, 1, 2]
+	);
+}
+
+/// glyph cards decode the same as range cards, at compile time
+#[cfg(feature = "macros")]
+#[test]
+fn glyph_macro() {
+	static ARROW: [u8; 5] = crate::punch! {
+		. . # . .
+		. # # # .
+		# . # . #
+		. . # . .
+	};
+	assert_eq!(ARROW, [2, 4, 15, 4, 2]);
+	let glyph = crate::punch! {
+		.###....
+		#...#...
+		#####...
+		#...#...
+		#...#...
+	};
+	assert_eq!(
+		glyph,
+		crate::include_card!("tests/glyph.card", '#', '.').punch_card()
+	);
+	assert_eq!(crate::punch! { 'X', '_'; X_XX }, [true, false, true, true]);
+	let flags = crate::punch! {
+		'X', '_';
+		X_XX
+		_X__
+	};
+	#[rustfmt::skip]
+	assert_eq!(flags, (..=.. ..=..=.., .. ..=.. .. ..,).punch_card());
+	// a `/` glyph isn't read as the start of a comment
+	#[rustfmt::skip]
+	let slashes: [u8; 2] = crate::punch! {
+		'/', '.';
+		. /
+		/ /
+	};
+	assert_eq!(slashes, [1, 3]);
+}
+
+/// stacked cards are read as limbs, past the 128 lines of a single card
+#[test]
+fn tall_limbs() {
+	#[rustfmt::skip]
+	let card = (
+		(
+			..=.. .. ..=..,
+			.. ..=.. ..=..,
+		),
+		(
+			.. .. .. ..=..,
+			..=.. ..=..=..,
+			.. ..=.. ..=..,
+		),
+	);
+	let bytes: [[u8; 1]; 4] = card.punch_card_limbs();
+	assert_eq!(bytes, [[0b10010], [0b01001], [0b00010], [0b11111]]);
+	let words: [[u16; 2]; 4] = card.punch_card_limbs();
+	assert_eq!(
+		words,
+		[[0, 0b10010], [0, 0b01001], [0, 0b00010], [0, 0b11111]]
+	);
+	#[cfg(feature = "macros")]
+	{
+		let massive = massive_card();
+		let limbs: [[u64; 4]; 256] = (
+			crate::include_card!("tests/massive.card"),
+			crate::include_card!("tests/massive.card"),
+		)
+			.punch_card_limbs();
+		for (column, limbs) in massive.iter().zip(limbs) {
+			let (high, low) = ((column >> 64) as u64, *column as u64);
+			assert_eq!(limbs, [high, low, high, low]);
+		}
+	}
+}
+
+#[cfg(feature = "nightly")]
 #[bench]
 fn massive_bench(b: &mut Bencher) {
 	b.iter(|| {